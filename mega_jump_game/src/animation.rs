@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_rapier2d::prelude::*;
+
+use crate::audio::SoundEffect;
+use crate::feel::CameraShake;
+use crate::{Player, PlayerState, PLAYER_SPEED};
+
+pub const PLAYER_TILESHEET: &str = "sprites/Adventurer/adventurer_tilesheet.png";
+// THE TILESHEET IS A 9 x 3 GRID OF 80 x 110 POSES
+const FRAME_SIZE: Vec2 = Vec2::new(80.0, 110.0);
+const SHEET_COLUMNS: usize = 9;
+const SHEET_ROWS: usize = 3;
+
+// INDEXES OF THE POSES IN THE TILESHEET, LEFT TO RIGHT, TOP TO BOTTOM
+const IDLE: usize = 0;
+const JUMP: usize = 1;
+const FALL: usize = 2;
+const DUCK: usize = 3;
+const HURT: usize = 4;
+const CHEER1: usize = 7;
+const CHEER2: usize = 8;
+const WALK1: usize = 9;
+const WALK2: usize = 10;
+const SKID: usize = 21;
+
+// SLOWER THAN THIS COUNTS AS STANDING STILL
+const WALK_SPEED_MIN: f32 = 10.0;
+// TURNING AROUND FROM AT LEAST THIS SPEED SHOWS THE SKID POSE FOR SKID_TIME SECONDS
+const SKID_SPEED_MIN: f32 = PLAYER_SPEED * 0.8;
+const SKID_TIME: f32 = 0.15;
+// LANDING FROM AT LEAST THIS FALL SPEED SQUASHES THE SPRITE BY UP TO SQUASH_AMOUNT FOR SQUASH_TIME SECONDS
+const LANDING_SPEED_MIN: f32 = 300.0;
+const SQUASH_AMOUNT: f32 = 0.2;
+const SQUASH_TIME: f32 = 0.12;
+const LANDING_TRAUMA: f32 = 0.2;
+
+// A SEQUENCE OF TILESHEET FRAMES SHOWN frame_time SECONDS EACH, HOLDING THE LAST ONE UNLESS looping
+pub struct AnimationClip {
+    pub frames: &'static [usize],
+    pub frame_time: f32,
+    pub looping: bool,
+}
+
+// THE CLIP PLAYED FOR EACH PlayerState
+pub fn player_clip(state: PlayerState) -> AnimationClip {
+    match state {
+        PlayerState::Idle => AnimationClip {
+            frames: &[IDLE],
+            frame_time: 1.0,
+            looping: false,
+        },
+        PlayerState::Left | PlayerState::Right => AnimationClip {
+            frames: &[WALK1, WALK2],
+            frame_time: 0.1,
+            looping: true,
+        },
+        PlayerState::Jump => AnimationClip {
+            frames: &[JUMP],
+            frame_time: 1.0,
+            looping: false,
+        },
+        PlayerState::Fall => AnimationClip {
+            frames: &[FALL],
+            frame_time: 1.0,
+            looping: false,
+        },
+        PlayerState::Hurt => AnimationClip {
+            frames: &[HURT],
+            frame_time: 1.0,
+            looping: false,
+        },
+        PlayerState::Cheer => AnimationClip {
+            frames: &[CHEER1, CHEER2],
+            frame_time: 0.25,
+            looping: true,
+        },
+        PlayerState::Skid => AnimationClip {
+            frames: &[SKID],
+            frame_time: 1.0,
+            looping: false,
+        },
+        PlayerState::Duck => AnimationClip {
+            frames: &[DUCK],
+            frame_time: 1.0,
+            looping: false,
+        },
+    }
+}
+
+// THE PLAYER'S TILESHEET CUT INTO FRAMES, BUILT ONCE AT STARTUP AND SHARED BY EVERY RUN
+#[derive(Resource)]
+pub struct PlayerAtlas(pub Handle<TextureAtlas>);
+
+pub fn load_player_atlas(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let atlas = TextureAtlas::from_grid(
+        asset_server.load(PLAYER_TILESHEET),
+        FRAME_SIZE,
+        SHEET_COLUMNS,
+        SHEET_ROWS,
+        None,
+        None,
+    );
+    commands.insert_resource(PlayerAtlas(texture_atlases.add(atlas)));
+}
+
+// WHERE THE PLAYER IS IN THE CLIP FOR THEIR CURRENT STATE, PLUS WHAT IS NEEDED TO NOTICE TURNS AND LANDINGS
+#[derive(Component, Default)]
+pub struct PlayerAnimation {
+    state: Option<PlayerState>,
+    frame: usize,
+    elapsed: f32,
+    facing_left: bool,
+    last_velocity: Vec2,
+    was_grounded: bool,
+    skid_left: f32,
+    squash_left: f32,
+}
+
+// PICKS THE PLAYER'S STATE FROM HOW THEY ARE ACTUALLY MOVING, HURT AND CHEER ARE SET BY THE SYSTEMS THAT CAUSE THEM
+pub fn select_player_state(
+    mut player_query: Query<(&mut Player, &Velocity, &mut PlayerAnimation)>,
+    keyboard: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut sound_events: EventWriter<SoundEffect>,
+    mut shake_events: EventWriter<CameraShake>,
+) {
+    let Ok((mut player, velocity, mut animation)) = player_query.get_single_mut() else {
+        return;
+    };
+    let velocity = velocity.linvel;
+    let grounded = player.grounded_on.is_some();
+
+    animation.skid_left = (animation.skid_left - time.delta_seconds()).max(0.0);
+    animation.squash_left = (animation.squash_left - time.delta_seconds()).max(0.0);
+    // velocity snaps straight to the new direction, so a turn shows up as a sign flip between frames
+    if grounded
+        && animation.last_velocity.x.abs() >= SKID_SPEED_MIN
+        && velocity.x * animation.last_velocity.x < 0.0
+    {
+        animation.skid_left = SKID_TIME;
+    }
+    if grounded && !animation.was_grounded && -animation.last_velocity.y >= LANDING_SPEED_MIN {
+        animation.squash_left = SQUASH_TIME;
+        sound_events.send(SoundEffect::Land);
+        shake_events.send(CameraShake { trauma: LANDING_TRAUMA });
+    }
+    // keep facing the old way while skidding, the pose leans against the turn
+    if animation.skid_left <= 0.0 && velocity.x.abs() > WALK_SPEED_MIN {
+        animation.facing_left = velocity.x < 0.0;
+    }
+    animation.last_velocity = velocity;
+    animation.was_grounded = grounded;
+
+    if player.knockback > 0.0 {
+        player.state = PlayerState::Hurt;
+        return;
+    }
+    player.state = if !grounded {
+        if velocity.y > 0.0 {
+            PlayerState::Jump
+        } else {
+            PlayerState::Fall
+        }
+    } else if animation.skid_left > 0.0 {
+        PlayerState::Skid
+    } else if velocity.x > WALK_SPEED_MIN {
+        PlayerState::Right
+    } else if velocity.x < -WALK_SPEED_MIN {
+        PlayerState::Left
+    } else if keyboard.pressed(KeyCode::Down) {
+        PlayerState::Duck
+    } else {
+        PlayerState::Idle
+    };
+}
+
+// RESTARTS THE CLIP WHENEVER THE PLAYER'S STATE CHANGES, STEPS THROUGH ITS FRAMES AND APPLIES FACING AND SQUASH
+pub fn animate_player(
+    mut player_query: Query<(&Player, &mut PlayerAnimation, &mut TextureAtlasSprite)>,
+    time: Res<Time>,
+) {
+    for (player, mut animation, mut sprite) in player_query.iter_mut() {
+        let clip = player_clip(player.state);
+        if animation.state != Some(player.state) {
+            animation.state = Some(player.state);
+            animation.frame = 0;
+            animation.elapsed = 0.0;
+        } else {
+            animation.elapsed += time.delta_seconds();
+            while animation.elapsed >= clip.frame_time {
+                animation.elapsed -= clip.frame_time;
+                animation.frame = if clip.looping {
+                    (animation.frame + 1) % clip.frames.len()
+                } else {
+                    (animation.frame + 1).min(clip.frames.len() - 1)
+                };
+            }
+        }
+
+        let index = clip.frames[animation.frame];
+        if sprite.index != index {
+            sprite.index = index;
+        }
+        sprite.flip_x = animation.facing_left;
+
+        // squash wider and flatter while keeping the feet where they are
+        let squash = SQUASH_AMOUNT * animation.squash_left / SQUASH_TIME;
+        if squash > 0.0 {
+            let size = FRAME_SIZE * Vec2::new(1.0 + squash, 1.0 - squash);
+            sprite.custom_size = Some(size);
+            sprite.anchor = Anchor::Custom(Vec2::new(0.0, FRAME_SIZE.y / (2.0 * size.y) - 0.5));
+        } else if sprite.custom_size.is_some() {
+            sprite.custom_size = None;
+            sprite.anchor = Anchor::Center;
+        }
+    }
+}
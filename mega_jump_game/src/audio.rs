@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+
+// PLAYED ON REPEAT FOR AS LONG AS THE GAME RUNS
+pub const MUSIC_TRACK: &str = "Child's Nightmare.ogg";
+const VOLUME_STEP: f32 = 0.1;
+
+// EVERY SOUND THE GAME MAKES OUTSIDE THE MUSIC, SEND ONE AS AN EVENT TO PLAY IT ON THE EFFECTS CHANNEL
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    Jump,
+    Land,
+    Crumble,
+    Vanish,
+    Blink,
+    Hurt,
+    Death,
+    ShieldBreak,
+    PowerUp,
+    Coin,
+}
+
+impl SoundEffect {
+    fn path(&self) -> &'static str {
+        match self {
+            SoundEffect::Jump => "audio/footstep_snow_000.ogg",
+            SoundEffect::Land => "audio/footstep_concrete_000.ogg",
+            SoundEffect::Crumble => "audio/impactWood_medium_000.ogg",
+            SoundEffect::Vanish | SoundEffect::ShieldBreak => "audio/impactGlass_heavy_001.ogg",
+            SoundEffect::Blink | SoundEffect::PowerUp => "audio/impactMetal_heavy_001.ogg",
+            SoundEffect::Hurt | SoundEffect::Death => "audio/impactPlate_medium_000.ogg",
+            SoundEffect::Coin => "audio/impactBell_heavy_000.ogg",
+        }
+    }
+}
+
+// LOUDNESS OF EACH CHANNEL FROM 0 TO 1, THE MASTER LEVEL SCALES BOTH OF THE OTHERS
+#[derive(Resource)]
+pub struct Volume {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+    pub music_muted: bool,
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.5,
+            effects: 0.8,
+            music_muted: false,
+        }
+    }
+}
+
+impl Volume {
+    pub fn music_level(&self) -> f32 {
+        if self.music_muted {
+            0.0
+        } else {
+            self.master * self.music
+        }
+    }
+
+    pub fn effects_level(&self) -> f32 {
+        self.master * self.effects
+    }
+}
+
+// THE TRACK THAT SHOULD BE PLAYING AND THE SINK OF THE ONE THAT IS, SET track TO CHANGE THE MUSIC
+#[derive(Resource)]
+pub struct Music {
+    pub track: &'static str,
+    playing: Option<&'static str>,
+    sink: Option<Handle<AudioSink>>,
+}
+
+impl Default for Music {
+    fn default() -> Self {
+        Self {
+            track: MUSIC_TRACK,
+            playing: None,
+            sink: None,
+        }
+    }
+}
+
+// STARTS THE WANTED TRACK LOOPING WHENEVER IT DIFFERS FROM THE ONE PLAYING, STOPPING THE OLD ONE
+// the ogg is decoded as it plays rather than all at once, so a long track doesn't stall the frame it starts on
+pub fn play_music(
+    mut music: ResMut<Music>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    volume: Res<Volume>,
+) {
+    if music.playing == Some(music.track) {
+        return;
+    }
+    if let Some(sink) = music.sink.take().and_then(|sink| audio_sinks.get(&sink)) {
+        sink.stop();
+    }
+
+    let settings = PlaybackSettings::LOOP.with_volume(volume.music_level());
+    let sink = audio.play_with_settings(asset_server.load(music.track), settings);
+    // the handle play gives back is weak, keep a strong one so the sink stays around to be controlled
+    music.sink = Some(audio_sinks.get_handle(sink));
+    music.playing = Some(music.track);
+}
+
+// KEEPS THE MUSIC AT THE MUSIC CHANNEL'S LEVEL, THE SINK ONLY EXISTS ONCE THE TRACK HAS LOADED
+pub fn apply_music_volume(music: Res<Music>, volume: Res<Volume>, audio_sinks: Res<Assets<AudioSink>>) {
+    let Some(sink) = music.sink.as_ref().and_then(|sink| audio_sinks.get(sink)) else {
+        return;
+    };
+    let level = volume.music_level();
+    if sink.volume() != level {
+        sink.set_volume(level);
+    }
+}
+
+// PLAYS EVERY SOUND EFFECT SENT THIS FRAME, THE SAME EFFECT ONLY ONCE
+pub fn play_sound_effects(
+    mut sound_events: EventReader<SoundEffect>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    volume: Res<Volume>,
+) {
+    let mut played = Vec::new();
+    for effect in sound_events.iter() {
+        if played.contains(effect) {
+            continue;
+        }
+        played.push(*effect);
+        audio.play_with_settings(
+            asset_server.load(effect.path()),
+            PlaybackSettings::ONCE.with_volume(volume.effects_level()),
+        );
+    }
+}
+
+// M MUTES OR UNMUTES THE MUSIC, MINUS AND EQUALS TURN EVERYTHING DOWN OR UP
+pub fn volume_input(keyboard: Res<Input<KeyCode>>, mut volume: ResMut<Volume>) {
+    if keyboard.just_pressed(KeyCode::M) {
+        volume.music_muted = !volume.music_muted;
+    }
+    if keyboard.just_pressed(KeyCode::Minus) {
+        volume.master = (volume.master - VOLUME_STEP).max(0.0);
+    }
+    if keyboard.just_pressed(KeyCode::Equals) {
+        volume.master = (volume.master + VOLUME_STEP).min(1.0);
+    }
+}
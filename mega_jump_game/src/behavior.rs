@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::audio::SoundEffect;
+use crate::Player;
+
+const CRUMBLING_COLOR: Color = Color::rgb(0.55, 0.35, 0.2);
+const VANISHING_COLOR: Color = Color::rgba(0.85, 0.95, 1.0, 0.6);
+const TIMED_COLOR: Color = Color::PURPLE;
+
+// SPECIAL RULES A PLATFORM CAN HAVE ON TOP OF BEING SOLID
+#[derive(Deserialize, Clone)]
+pub enum PlatformBehavior {
+    // BREAKS AFTER THE PLAYER HAS STOOD ON IT FOR delay SECONDS IN TOTAL
+    Crumbling { delay: f32 },
+    // DISAPPEARS AS SOON AS THE PLAYER LEAVES IT AFTER LANDING ONCE
+    Vanishing,
+    // SOLID FOR on SECONDS THEN GONE FOR off SECONDS, offset SHIFTS WHERE IN THE CYCLE IT STARTS
+    Timed {
+        on: f32,
+        off: f32,
+        #[serde(default)]
+        offset: f32,
+    },
+}
+
+impl PlatformBehavior {
+    // EVERY BEHAVIOR HAS ITS OWN COLOR SO THE PLAYER CAN TELL THEM APART
+    pub fn color(&self) -> Color {
+        match self {
+            PlatformBehavior::Crumbling { .. } => CRUMBLING_COLOR,
+            PlatformBehavior::Vanishing => VANISHING_COLOR,
+            PlatformBehavior::Timed { .. } => TIMED_COLOR,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SpecialPlatform {
+    pub behavior: PlatformBehavior,
+    timer: f32,
+    landed: bool,
+    solid: bool,
+}
+
+impl SpecialPlatform {
+    pub fn new(behavior: PlatformBehavior) -> Self {
+        let timer = match &behavior {
+            PlatformBehavior::Timed { offset, .. } => *offset,
+            _ => 0.0,
+        };
+        Self {
+            behavior,
+            timer,
+            landed: false,
+            solid: true,
+        }
+    }
+}
+
+// RUNS THE CRUMBLE, VANISH AND BLINK RULES FOR EVERY SPECIAL PLATFORM
+pub fn update_special_platforms(
+    mut commands: Commands,
+    player_query: Query<&Player>,
+    mut platform_query: Query<(Entity, &mut SpecialPlatform, &mut Visibility)>,
+    time: Res<Time>,
+    mut sound_events: EventWriter<SoundEffect>,
+) {
+    let grounded_on = player_query.get_single().ok().and_then(|player| player.grounded_on);
+
+    for (entity, mut platform, mut visibility) in platform_query.iter_mut() {
+        let under_player = grounded_on == Some(entity);
+
+        match platform.behavior {
+            PlatformBehavior::Crumbling { delay } => {
+                if under_player {
+                    platform.timer += time.delta_seconds();
+                }
+                if platform.timer >= delay {
+                    sound_events.send(SoundEffect::Crumble);
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            PlatformBehavior::Vanishing => {
+                if under_player {
+                    platform.landed = true;
+                } else if platform.landed {
+                    sound_events.send(SoundEffect::Vanish);
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            PlatformBehavior::Timed { on, off, .. } => {
+                platform.timer = (platform.timer + time.delta_seconds()) % (on + off).max(f32::EPSILON);
+                let solid = platform.timer < on;
+                if solid == platform.solid {
+                    continue;
+                }
+                platform.solid = solid;
+                if solid {
+                    *visibility = Visibility::Inherited;
+                    commands.entity(entity).remove::<ColliderDisabled>();
+                } else {
+                    if under_player {
+                        sound_events.send(SoundEffect::Blink);
+                    }
+                    *visibility = Visibility::Hidden;
+                    commands.entity(entity).insert(ColliderDisabled);
+                }
+            }
+        }
+    }
+}
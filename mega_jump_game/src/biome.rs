@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::audio::{Music, MUSIC_TRACK};
+use crate::cloud::CloudCover;
+use crate::parallax::{ParallaxLayer, ParallaxTile};
+use crate::BACKGROUND_COLOR;
+
+// THE LOOK OF THE CLIMB BLENDS FROM ONE BIOME TO THE NEXT OVER THIS MANY PIXELS, CENTRED ON THE BOUNDARY
+const FADE_DISTANCE: f32 = 800.0;
+
+// EVERYTHING A BIOME CHANGES THAT CAN BE BLENDED BETWEEN TWO BIOMES
+// only the desert mountains set ships, so higher biomes re-tint its layers or fade them out
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub clear_color: Color,
+    pub layer_tints: [Color; 3], // one per parallax layer, farthest first, a clear tint hides the layer
+    pub platform_color: Color,
+    pub cloud_scale: f32, // multiplies the cloud cover's density
+}
+
+impl Theme {
+    // t OF 0.0 IS THIS THEME, 1.0 IS other
+    fn blend(&self, other: &Theme, t: f32) -> Theme {
+        Theme {
+            clear_color: blend_color(self.clear_color, other.clear_color, t),
+            layer_tints: [
+                blend_color(self.layer_tints[0], other.layer_tints[0], t),
+                blend_color(self.layer_tints[1], other.layer_tints[1], t),
+                blend_color(self.layer_tints[2], other.layer_tints[2], t),
+            ],
+            platform_color: blend_color(self.platform_color, other.platform_color, t),
+            cloud_scale: self.cloud_scale + (other.cloud_scale - self.cloud_scale) * t,
+        }
+    }
+}
+
+fn blend_color(from: Color, to: Color, t: f32) -> Color {
+    let from = from.as_rgba_f32();
+    let to = to.as_rgba_f32();
+    let mix = |i: usize| from[i] + (to[i] - from[i]) * t;
+    Color::rgba(mix(0), mix(1), mix(2), mix(3))
+}
+
+// A PLATFORM RE-TINTED TO MATCH THE BIOME WHILE IT IS ON SCREEN, SEE PlatformDesc::biome_tint
+#[derive(Component)]
+pub struct BiomeTinted;
+
+// A STRETCH OF THE CLIMB STARTING start_height PIXELS ABOVE THE STARTING PLATFORM
+pub struct Biome {
+    pub start_height: f32,
+    pub music: &'static str, // every biome shares the one track until more ship
+    pub theme: Theme,
+}
+
+// LOWEST FIRST
+pub const BIOMES: [Biome; 4] = [
+    // DESERT
+    Biome {
+        start_height: 0.0,
+        music: MUSIC_TRACK,
+        theme: Theme {
+            clear_color: BACKGROUND_COLOR,
+            layer_tints: [Color::WHITE, Color::WHITE, Color::WHITE],
+            platform_color: Color::ORANGE_RED,
+            cloud_scale: 1.0,
+        },
+    },
+    // SKY
+    Biome {
+        start_height: 3000.0,
+        music: MUSIC_TRACK,
+        theme: Theme {
+            clear_color: Color::rgb(0.55, 0.8, 1.0),
+            layer_tints: [Color::rgb(0.8, 0.9, 1.0), Color::NONE, Color::NONE],
+            platform_color: Color::ALICE_BLUE,
+            cloud_scale: 1.5,
+        },
+    },
+    // STORM
+    Biome {
+        start_height: 7000.0,
+        music: MUSIC_TRACK,
+        theme: Theme {
+            clear_color: Color::rgb(0.2, 0.22, 0.3),
+            layer_tints: [Color::rgb(0.35, 0.38, 0.45), Color::NONE, Color::NONE],
+            platform_color: Color::GRAY,
+            cloud_scale: 2.5,
+        },
+    },
+    // SPACE
+    Biome {
+        start_height: 12000.0,
+        music: MUSIC_TRACK,
+        theme: Theme {
+            clear_color: Color::rgb(0.02, 0.02, 0.08),
+            layer_tints: [Color::NONE, Color::NONE, Color::NONE],
+            platform_color: Color::SILVER,
+            cloud_scale: 0.0,
+        },
+    },
+];
+
+// THE BIOME WHOSE STRETCH height IS IN
+pub fn biome_at(height: f32) -> &'static Biome {
+    BIOMES
+        .iter()
+        .rev()
+        .find(|biome| height >= biome.start_height)
+        .unwrap_or(&BIOMES[0])
+}
+
+// THE THEME AT height, HALFWAY BETWEEN TWO BIOMES RIGHT ON THEIR BOUNDARY
+pub fn theme_at(height: f32) -> Theme {
+    let index = BIOMES
+        .iter()
+        .rposition(|biome| height + FADE_DISTANCE / 2.0 >= biome.start_height)
+        .unwrap_or(0);
+    if index == 0 {
+        return BIOMES[0].theme;
+    }
+    let fade_start = BIOMES[index].start_height - FADE_DISTANCE / 2.0;
+    let t = ((height - fade_start) / FADE_DISTANCE).clamp(0.0, 1.0);
+    BIOMES[index - 1].theme.blend(&BIOMES[index].theme, t)
+}
+
+// HEIGHT OF THE MIDDLE OF THE VIEW ABOVE THE STARTING PLATFORM, WHICH SITS HALF A WINDOW UP
+fn view_height(camera_transform: &Transform, window: &Window) -> f32 {
+    camera_transform.translation.y - window.height() / 2.0
+}
+
+// TINTS THE SKY, THE PARALLAX LAYERS AND THE PLATFORMS ON SCREEN, THINS OR THICKENS THE CLOUDS AND PICKS THE MUSIC FOR WHERE THE CAMERA IS
+#[allow(clippy::too_many_arguments)]
+pub fn apply_biome(
+    camera_query: Query<&Transform, With<Camera2d>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    layer_query: Query<&ParallaxLayer>,
+    mut tile_query: Query<(&Parent, &mut Sprite), With<ParallaxTile>>,
+    platform_query: Query<(&Transform, &Handle<ColorMaterial>), With<BiomeTinted>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+    mut cloud_cover: ResMut<CloudCover>,
+    mut music: ResMut<Music>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let window: &Window = window_query.get_single().unwrap();
+    let height = view_height(camera_transform, window);
+    let theme = theme_at(height);
+
+    if clear_color.0 != theme.clear_color {
+        clear_color.0 = theme.clear_color;
+    }
+    for (parent, mut sprite) in tile_query.iter_mut() {
+        let Ok(layer) = layer_query.get(parent.get()) else {
+            continue;
+        };
+        let tint = theme.layer_tints[layer.index];
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+    if cloud_cover.biome_scale != theme.cloud_scale {
+        cloud_cover.biome_scale = theme.cloud_scale;
+    }
+    let track = biome_at(height).music;
+    if music.track != track {
+        music.track = track;
+    }
+    // every platform gets its own material, so re-tinting one leaves the rest alone
+    let camera_y = camera_transform.translation.y;
+    for (transform, handle) in platform_query.iter() {
+        if (transform.translation.y - camera_y).abs() > window.height() {
+            continue;
+        }
+        let Some(material) = materials.get(handle) else {
+            continue;
+        };
+        if material.color != theme.platform_color {
+            materials.get_mut(handle).unwrap().color = theme.platform_color;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_rapier2d::prelude::*;
+
+use crate::classic::GameMode;
+use crate::{Player, JUMP_FORCE, PLAYER_SIZE, PLAYER_SPEED};
+
+// HOW FAR ABOVE THE PLAYER THE CAMERA AIMS SO THEY ARE LOW ON SCREEN AND CAN SEE THE PLATFORMS ABOVE
+const VIEW_OFFSET: f32 = 150.0;
+// THE CAMERA NEVER LETS THE PLAYER GET CLOSER THAN THIS TO THE TOP OR BOTTOM OF THE WINDOW
+const EDGE_MARGIN: f32 = PLAYER_SIZE * 1.5;
+// SET TO 1 OR true TO PLAY BY THE DOODLE JUMP RULE OUTSIDE CLASSIC MODE TOO
+pub const NEVER_SCROLL_DOWN_ENV_VAR: &str = "MEGA_JUMP_NEVER_SCROLL_DOWN";
+
+// SENT WHENEVER THE PLAYER IS PUT SOMEWHERE NEW, SO THE CAMERA JUMPS THERE INSTEAD OF GLIDING OR REFUSING TO SCROLL DOWN
+pub struct SnapCamera;
+
+// HOW THE CAMERA FOLLOWS THE PLAYER, KEPT ON THE ONE CAMERA SPAWNED IN setup
+#[derive(Component)]
+pub struct CameraController {
+    pub smoothing: f32,          // how quickly the camera closes the gap to where it wants to be, per second
+    pub dead_zone: f32,          // the player can move this far up or down before the camera starts following
+    pub look_ahead: Vec2,        // how far ahead the camera looks at full speed in each direction
+    pub lock_x: bool,            // keep the camera on the middle of the one window wide play field
+    pub never_scroll_down: bool, // the doodle jump rule, classic mode always plays by it
+    position: Vec2,
+    focus_y: f32, // the middle of the dead zone
+}
+
+impl CameraController {
+    // A CONTROLLER FOR A CAMERA STARTING OUT AT position
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            focus_y: position.y,
+            ..default()
+        }
+    }
+
+    // LIKE new, BUT TURNS never_scroll_down ON WHEN MEGA_JUMP_NEVER_SCROLL_DOWN ASKS FOR IT
+    pub fn from_env(position: Vec2) -> Self {
+        let never_scroll_down = std::env::var(NEVER_SCROLL_DOWN_ENV_VAR)
+            .map(|value| matches!(value.trim(), "1" | "true"))
+            .unwrap_or(false);
+        Self {
+            never_scroll_down,
+            ..Self::new(position)
+        }
+    }
+
+    // WHERE THE CONTROLLER WANTS THE CAMERA, BEFORE ANY SHAKE
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            smoothing: 6.0,
+            dead_zone: 60.0,
+            look_ahead: Vec2::new(80.0, 120.0),
+            lock_x: true,
+            never_scroll_down: false,
+            position: Vec2::ZERO,
+            focus_y: 0.0,
+        }
+    }
+}
+
+// EASES THE CAMERA TOWARDS THE PLAYER, LOOKING AHEAD OF WHERE THEY ARE GOING AND IGNORING SMALL HOPS
+pub fn camera_follow(
+    player_query: Query<(&Transform, &Velocity), With<Player>>,
+    new_player_query: Query<(), Added<Player>>,
+    mut camera_query: Query<(&mut CameraController, &mut Transform), Without<Player>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut snap_events: EventReader<SnapCamera>,
+    game_mode: Res<GameMode>,
+    time: Res<Time>,
+) {
+    let Ok((player_transform, velocity)) = player_query.get_single() else {
+        return;
+    };
+    let Ok((mut controller, mut camera_transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+    let window: &Window = window_query.get_single().unwrap();
+    let player = player_transform.translation.truncate();
+    let snap = !new_player_query.is_empty() || snap_events.iter().count() > 0;
+
+    // the dead zone only moves once the player pushes against one of its edges
+    let player_focus = player.y + VIEW_OFFSET;
+    controller.focus_y = if snap {
+        player_focus
+    } else {
+        controller
+            .focus_y
+            .clamp(player_focus - controller.dead_zone, player_focus + controller.dead_zone)
+    };
+
+    let heading = Vec2::new(
+        (velocity.linvel.x / PLAYER_SPEED).clamp(-1.0, 1.0),
+        (velocity.linvel.y / JUMP_FORCE).clamp(-1.0, 1.0),
+    );
+    let look_ahead = controller.look_ahead * heading;
+    let target = Vec2::new(
+        if controller.lock_x { window.width() / 2.0 } else { player.x + look_ahead.x },
+        controller.focus_y + look_ahead.y,
+    );
+
+    let mut position = if snap {
+        target
+    } else {
+        // exponential smoothing, the same feel whatever the frame rate
+        let blend = 1.0 - (-controller.smoothing * time.delta_seconds()).exp();
+        controller.position + (target - controller.position) * blend
+    };
+
+    let only_up = controller.never_scroll_down || *game_mode == GameMode::Classic;
+    if only_up && !snap {
+        position.y = position.y.max(controller.position.y);
+    }
+    // never lag so far behind that the player leaves the window, except off the bottom under the doodle jump rule
+    let half_height = window.height() / 2.0 - EDGE_MARGIN;
+    position.y = position.y.max(player.y - half_height);
+    if !only_up {
+        position.y = position.y.min(player.y + half_height);
+    }
+
+    controller.position = position;
+    camera_transform.translation.x = position.x;
+    camera_transform.translation.y = position.y;
+}
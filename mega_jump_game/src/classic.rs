@@ -0,0 +1,59 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::Ground;
+
+// HOW FAR FROM STRAIGHT UP A CONTACT CAN BE AND STILL LAND ON A ONE WAY PLATFORM, IN RADIANS
+const ONE_WAY_ALLOWED_ANGLE: f32 = 0.6;
+
+// STANDARD NEEDS SPACE TO JUMP, CLASSIC BOUNCES ON LANDING AND LETS THE PLAYER JUMP UP THROUGH PLATFORMS
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Classic,
+}
+
+impl GameMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            GameMode::Standard => GameMode::Classic,
+            GameMode::Classic => GameMode::Standard,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Standard => "Standard",
+            GameMode::Classic => "Classic",
+        }
+    }
+}
+
+// RAPIER HOOKS THAT TURN EVERY PLATFORM, SPRING AND TRAMPOLINE INTO A ONE WAY PLATFORM WHILE IN CLASSIC MODE
+#[derive(SystemParam)]
+pub struct OneWayPlatformHooks<'w, 's> {
+    game_mode: Res<'w, GameMode>,
+    platforms: Query<'w, 's, (), Ground>,
+}
+
+impl BevyPhysicsHooks for OneWayPlatformHooks<'_, '_> {
+    fn modify_solver_contacts(&self, context: ContactModificationContextView) {
+        if *self.game_mode != GameMode::Classic {
+            return;
+        }
+
+        // the allowed normal is in the first collider's space and points towards the second one
+        let allowed_normal = if self.platforms.contains(context.collider1()) {
+            Vect::Y
+        } else if self.platforms.contains(context.collider2()) {
+            -Vect::Y
+        } else {
+            return;
+        };
+        context
+            .raw
+            .update_as_oneway_platform(&allowed_normal.into(), ONE_WAY_ALLOWED_ANGLE);
+    }
+}
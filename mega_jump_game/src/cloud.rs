@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const CLOUD_IMAGES: [&str; 8] = [
+    "backgrounds/BG_DesertMountains/cloud1.png",
+    "backgrounds/BG_DesertMountains/cloud2.png",
+    "backgrounds/BG_DesertMountains/cloud3.png",
+    "backgrounds/BG_DesertMountains/cloud4.png",
+    "backgrounds/BG_DesertMountains/cloud5.png",
+    "backgrounds/BG_DesertMountains/cloud6.png",
+    "backgrounds/BG_DesertMountains/cloud7.png",
+    "backgrounds/BG_DesertMountains/cloud8.png",
+];
+// SET TO THE NUMBER OF CLOUDS AROUND THE CAMERA AND THE SHARE OF THEM IN FRONT, E.G. "12,0.5"
+pub const CLOUD_ENV_VAR: &str = "MEGA_JUMP_CLOUDS";
+const DEFAULT_CLOUD_DENSITY: f32 = 6.0;
+const DEFAULT_FRONT_SHARE: f32 = 0.3;
+// THE CLOUD SPRITES ARE TINY, SO THEY ARE DRAWN THIS MANY TIMES THEIR SIZE
+const MIN_CLOUD_SCALE: f32 = 2.0;
+const MAX_CLOUD_SCALE: f32 = 4.0;
+const MIN_DRIFT_SPEED: f32 = 15.0;
+const MAX_DRIFT_SPEED: f32 = 70.0;
+// CLOUDS IN FRONT ARE SEE THROUGH ENOUGH TO MAKE OUT WHAT IS BEHIND THEM, UNLESS THE SCREEN IS CROWDED
+const MIN_FRONT_ALPHA: f32 = 0.35;
+const MAX_FRONT_ALPHA: f32 = 0.75;
+const MIN_BEHIND_ALPHA: f32 = 0.6;
+const MAX_BEHIND_ALPHA: f32 = 1.0;
+// BEHIND PLATFORMS AND THE PLAYER BUT IN FRONT OF THE PARALLAX LAYERS, OR IN FRONT OF EVERYTHING
+const BEHIND_Z: f32 = -1.0;
+const FRONT_Z: f32 = 5.0;
+// HOW FAR OFF SCREEN A CLOUD GOES BEFORE IT WRAPS AROUND OR IS MOVED BACK ABOVE THE CAMERA
+const OFF_SCREEN_MARGIN: f32 = 300.0;
+
+// THE DIFFICULTY KNOB FOR HOW MUCH OF THE PLAY AREA THE CLOUDS HIDE
+#[derive(Resource)]
+pub struct CloudCover {
+    pub density: f32,     // clouds kept around the camera
+    pub front_share: f32, // 0.0 puts every cloud behind the platforms, 1.0 puts every cloud in front
+    pub biome_scale: f32, // set by the biome the camera is in
+    rng: StdRng,
+}
+
+impl Default for CloudCover {
+    fn default() -> Self {
+        Self {
+            density: DEFAULT_CLOUD_DENSITY,
+            front_share: DEFAULT_FRONT_SHARE,
+            biome_scale: 1.0,
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl CloudCover {
+    // READS "density,front_share" FROM MEGA_JUMP_CLOUDS, EITHER PART CAN BE LEFT OUT FOR ITS DEFAULT
+    pub fn from_env() -> Self {
+        let mut cover = Self::default();
+        let Ok(value) = std::env::var(CLOUD_ENV_VAR) else {
+            return cover;
+        };
+        let mut parts = value.split(',').map(|part| part.trim().parse::<f32>().ok());
+        if let Some(Some(density)) = parts.next() {
+            cover.density = density.max(0.0);
+        }
+        if let Some(Some(front_share)) = parts.next() {
+            cover.front_share = front_share.clamp(0.0, 1.0);
+        }
+        cover
+    }
+}
+
+// A CLOUD DRIFTING SIDEWAYS AT speed, NEGATIVE SPEEDS DRIFT LEFT
+#[derive(Component)]
+pub struct Cloud {
+    speed: f32,
+}
+
+// PICKS A NEW LOOK, SPEED AND LAYER FOR A CLOUD, USED BOTH FOR NEW CLOUDS AND FOR ONES MOVED BACK ABOVE THE CAMERA
+fn roll_cloud(cover: &mut CloudCover, asset_server: &AssetServer) -> (Cloud, Handle<Image>, Sprite, f32, f32) {
+    let front_share = cover.front_share;
+    let rng = &mut cover.rng;
+    let image = asset_server.load(CLOUD_IMAGES[rng.gen_range(0..CLOUD_IMAGES.len())]);
+    let speed = rng.gen_range(MIN_DRIFT_SPEED..=MAX_DRIFT_SPEED) * if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+    let in_front = rng.gen::<f32>() < front_share;
+    let (alpha, z) = if in_front {
+        (rng.gen_range(MIN_FRONT_ALPHA..=MAX_FRONT_ALPHA), FRONT_Z)
+    } else {
+        (rng.gen_range(MIN_BEHIND_ALPHA..=MAX_BEHIND_ALPHA), BEHIND_Z)
+    };
+    let sprite = Sprite {
+        color: Color::rgba(1.0, 1.0, 1.0, alpha),
+        ..default()
+    };
+    let scale = rng.gen_range(MIN_CLOUD_SCALE..=MAX_CLOUD_SCALE);
+    (Cloud { speed }, image, sprite, z, scale)
+}
+
+// SPAWNS OR REMOVES CLOUDS UNTIL THERE ARE AS MANY AS THE CLOUD COVER ASKS FOR
+// the first clouds fill the screen, later ones start out of sight above it and extra ones are only removed out of sight
+pub fn maintain_cloud_count(
+    mut commands: Commands,
+    mut cover: ResMut<CloudCover>,
+    cloud_query: Query<(Entity, &Transform), With<Cloud>>,
+    camera_query: Query<&Transform, (With<Camera2d>, Without<Cloud>)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let window: &Window = window_query.get_single().unwrap();
+    let camera_y = camera_transform.translation.y;
+    let target = (cover.density * cover.biome_scale).round() as usize;
+    let count = cloud_query.iter().count();
+    if count > target {
+        let out_of_sight = cloud_query
+            .iter()
+            .filter(|(_, transform)| (transform.translation.y - camera_y).abs() > window.height() / 2.0 + OFF_SCREEN_MARGIN / 2.0);
+        for (entity, _) in out_of_sight.take(count - target) {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let (lowest_y, highest_y) = if count == 0 {
+        (camera_y - window.height() / 2.0, camera_y + window.height() / 2.0)
+    } else {
+        let top = camera_y + window.height() / 2.0 + OFF_SCREEN_MARGIN;
+        (top, top + window.height())
+    };
+
+    for _ in count..target {
+        let (cloud, texture, sprite, z, scale) = roll_cloud(&mut cover, &asset_server);
+        let x = cover.rng.gen_range(0.0..=window.width());
+        let y = cover.rng.gen_range(lowest_y..=highest_y);
+        commands.spawn((
+            cloud,
+            SpriteBundle {
+                texture,
+                sprite,
+                transform: Transform::from_xyz(x, y, z).with_scale(Vec3::splat(scale)),
+                ..default()
+            },
+        ));
+    }
+}
+
+// DRIFTS CLOUDS SIDEWAYS, WRAPPING THEM AROUND THE WINDOW, AND MOVES ONES THE CAMERA HAS LEFT BEHIND BACK IN ITS WAY
+pub fn drift_clouds(
+    mut cover: ResMut<CloudCover>,
+    mut cloud_query: Query<(&mut Cloud, &mut Transform, &mut Handle<Image>, &mut Sprite)>,
+    camera_query: Query<&Transform, (With<Camera2d>, Without<Cloud>)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let window: &Window = window_query.get_single().unwrap();
+    let bottom = camera_transform.translation.y - window.height() / 2.0 - OFF_SCREEN_MARGIN;
+    let top = camera_transform.translation.y + window.height() / 2.0 + OFF_SCREEN_MARGIN;
+
+    for (mut cloud, mut transform, mut texture, mut sprite) in cloud_query.iter_mut() {
+        transform.translation.x += cloud.speed * time.delta_seconds();
+        if transform.translation.x > window.width() + OFF_SCREEN_MARGIN {
+            transform.translation.x = -OFF_SCREEN_MARGIN;
+        } else if transform.translation.x < -OFF_SCREEN_MARGIN {
+            transform.translation.x = window.width() + OFF_SCREEN_MARGIN;
+        }
+
+        // a cloud far above means the camera jumped down, like at the start of a new run, so bring it into view
+        let y = if transform.translation.y < bottom {
+            top + cover.rng.gen_range(0.0..=window.height())
+        } else if transform.translation.y > top + window.height() {
+            cover.rng.gen_range(bottom + OFF_SCREEN_MARGIN..=top - OFF_SCREEN_MARGIN)
+        } else {
+            continue;
+        };
+        let (new_cloud, new_texture, new_sprite, z, scale) = roll_cloud(&mut cover, &asset_server);
+        *cloud = new_cloud;
+        *texture = new_texture;
+        *sprite = new_sprite;
+        transform.translation.y = y;
+        transform.translation.z = z;
+        transform.scale = Vec3::splat(scale);
+    }
+}
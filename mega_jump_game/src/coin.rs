@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::prelude::*;
+
+use crate::audio::SoundEffect;
+use crate::score::Score;
+use crate::shop::Wallet;
+use crate::Player;
+
+pub const COIN_RADIUS: f32 = 12.0;
+const COIN_COLOR: Color = Color::GOLD;
+// A COLLECTED COIN FLOATS UP AND SHRINKS AWAY OVER THIS MANY SECONDS
+const PICKUP_TIME: f32 = 0.4;
+const PICKUP_RISE: f32 = 60.0;
+
+#[derive(Component)]
+pub struct Coin;
+
+// A COIN THAT HAS JUST BEEN COLLECTED AND IS PLAYING ITS PICKUP ANIMATION, STILL CLEANED UP WITH THE RUN
+#[derive(Component)]
+pub struct CoinPickup {
+    elapsed: f32,
+    start_y: f32,
+}
+
+pub fn spawn_coin(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    origin: Vec2,
+    offset: Vec2,
+) -> Entity {
+    let position = origin + offset;
+    commands
+        .spawn((
+            Coin,
+            Collider::ball(COIN_RADIUS),
+            Sensor,
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Mesh::from(shape::Circle::new(COIN_RADIUS))).into(),
+                material: materials.add(ColorMaterial::from(COIN_COLOR)),
+                transform: Transform::from_xyz(position.x, position.y, 0.2),
+                ..default()
+            },
+        ))
+        .id()
+}
+
+// COUNTS EVERY COIN THE PLAYER TOUCHES INTO THE RUN AND THE WALLET AND STARTS ITS PICKUP ANIMATION
+pub fn collect_coins(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    player_query: Query<Entity, With<Player>>,
+    coin_query: Query<&Transform, With<Coin>>,
+    mut score: ResMut<Score>,
+    mut wallet: ResMut<Wallet>,
+    mut sound_events: EventWriter<SoundEffect>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for (collider1, collider2, intersecting) in rapier_context.intersections_with(player_entity) {
+        if !intersecting {
+            continue;
+        }
+        let other = if collider1 == player_entity { collider2 } else { collider1 };
+        let Ok(coin_transform) = coin_query.get(other) else {
+            continue;
+        };
+
+        score.coins += 1;
+        wallet.coins += 1;
+        sound_events.send(SoundEffect::Coin);
+        commands
+            .entity(other)
+            .remove::<(Coin, Collider, Sensor)>()
+            .insert(CoinPickup {
+                elapsed: 0.0,
+                start_y: coin_transform.translation.y,
+            });
+    }
+}
+
+// FLOATS COLLECTED COINS UPWARDS WHILE SHRINKING THEM, THEN REMOVES THEM
+pub fn animate_coin_pickups(
+    mut commands: Commands,
+    mut pickup_query: Query<(Entity, &mut CoinPickup, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut pickup, mut transform) in pickup_query.iter_mut() {
+        pickup.elapsed += time.delta_seconds();
+        let t = (pickup.elapsed / PICKUP_TIME).min(1.0);
+        transform.translation.y = pickup.start_y + PICKUP_RISE * t;
+        transform.scale = Vec3::splat(1.0 - t);
+        if t >= 1.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
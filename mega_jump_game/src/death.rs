@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::health::LifeLost;
+use crate::map::Platform;
+use crate::{Player, PLAYER_SIZE};
+
+// HOW FAR BELOW THE HIGHEST PLATFORM TOUCHED THE PLAYER CAN FALL BEFORE THE RUN ENDS
+pub const FALL_DEATH_DISTANCE: f32 = 1000.0;
+
+// WORLD Y OF THE HIGHEST PLATFORM THE PLAYER HAS STOOD ON THIS RUN
+#[derive(Resource, Default)]
+pub struct HighestPlatform(pub Option<f32>);
+
+pub fn reset_highest_platform(mut highest_platform: ResMut<HighestPlatform>) {
+    highest_platform.0 = None;
+}
+
+// KEEPS THE HIGHEST PLATFORM THE PLAYER HAS STOOD ON
+pub fn track_highest_platform(
+    player_query: Query<&Player>,
+    platform_query: Query<&Transform, With<Platform>>,
+    mut highest_platform: ResMut<HighestPlatform>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let Some(platform_transform) = player.grounded_on.and_then(|platform| platform_query.get(platform).ok()) else {
+        return;
+    };
+
+    let platform_y = platform_transform.translation.y;
+    let higher = match highest_platform.0 {
+        Some(highest) => platform_y > highest,
+        None => true,
+    };
+    if higher {
+        highest_platform.0 = Some(platform_y);
+    }
+}
+
+// COSTS A LIFE WHEN THE PLAYER FALLS TOO FAR BELOW THEIR BEST PLATFORM OR OUT OF THE CAMERA VIEW
+pub fn check_fall_death(
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&Transform, (With<Camera2d>, Without<Player>)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    highest_platform: Res<HighestPlatform>,
+    mut life_lost_events: EventWriter<LifeLost>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let window: &Window = window_query.get_single().unwrap();
+    let player_y = player_transform.translation.y;
+
+    let fell_too_far = highest_platform
+        .0
+        .is_some_and(|highest| player_y < highest - FALL_DEATH_DISTANCE);
+    let below_camera = camera_query.get_single().is_ok_and(|camera_transform| {
+        player_y < camera_transform.translation.y - window.height() / 2.0 - PLAYER_SIZE
+    });
+
+    if fell_too_far || below_camera {
+        life_lost_events.send(LifeLost);
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::camera::CameraController;
+
+// TRAUMA LEFT AFTER A SECOND OF CALM, THE SHAKE ITSELF GROWS WITH THE SQUARE OF THE TRAUMA
+const TRAUMA_DECAY: f32 = 1.5;
+const MAX_SHAKE_OFFSET: f32 = 24.0;
+const MAX_SHAKE_ANGLE: f32 = 0.05; // radians
+const SHAKE_FREQUENCY: f32 = 25.0;
+// A ZOOM PUNCH WEARS OFF BY THIS FACTOR EVERY SECOND, AND CAN'T ZOOM IN BY MORE THAN MAX_ZOOM_PUNCH
+const ZOOM_RECOVERY: f32 = 8.0;
+const MAX_ZOOM_PUNCH: f32 = 0.3;
+const FLASH_TIME: f32 = 0.25;
+
+// ADDS trauma (0 TO 1) TO THE CAMERA, TRAUMA ADDS UP FROM SEVERAL EVENTS AND FADES OVER TIME
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+// ZOOMS THE CAMERA IN BY strength (A FRACTION OF THE VIEW) AND LETS IT SPRING BACK
+pub struct ZoomPunch {
+    pub strength: f32,
+}
+
+// COVERS THE SCREEN IN color, FADING OUT FROM ITS ALPHA OVER FLASH_TIME SECONDS
+pub struct ScreenFlash {
+    pub color: Color,
+}
+
+// THE SHAKE AND ZOOM STILL PLAYING ON THE CAMERA
+#[derive(Component, Default)]
+pub struct CameraFeel {
+    trauma: f32,
+    zoom: f32,
+    elapsed: f32,
+}
+
+// THE FULL SCREEN NODE THE FLASH IS DRAWN ON AND THE FLASH STILL FADING ON IT
+#[derive(Component)]
+pub struct FlashOverlay {
+    color: Color,
+    remaining: f32,
+}
+
+pub fn spawn_flash_overlay(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        FlashOverlay {
+            color: Color::NONE,
+            remaining: 0.0,
+        },
+    ));
+}
+
+// SHAKES AND ZOOMS THE CAMERA AROUND WHERE ITS CONTROLLER PUT IT, SO THE SHAKE NEVER DRIFTS THE VIEW
+pub fn apply_camera_feel(
+    mut shake_events: EventReader<CameraShake>,
+    mut zoom_events: EventReader<ZoomPunch>,
+    mut camera_query: Query<(&mut CameraFeel, &CameraController, &mut Transform, &mut OrthographicProjection)>,
+    time: Res<Time>,
+) {
+    let Ok((mut feel, controller, mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+    for shake in shake_events.iter() {
+        feel.trauma = (feel.trauma + shake.trauma).min(1.0);
+    }
+    for punch in zoom_events.iter() {
+        feel.zoom = feel.zoom.max(punch.strength).min(MAX_ZOOM_PUNCH);
+    }
+
+    let dt = time.delta_seconds();
+    feel.elapsed += dt;
+    feel.trauma = (feel.trauma - TRAUMA_DECAY * dt).max(0.0);
+    feel.zoom *= (-ZOOM_RECOVERY * dt).exp();
+
+    // overlapping sine waves make a wobble that doesn't visibly repeat
+    let shake = feel.trauma * feel.trauma;
+    let t = feel.elapsed * SHAKE_FREQUENCY;
+    let wobble = |phase: f32| ((t + phase).sin() + (t * 1.7 + phase * 2.3).sin() * 0.5) / 1.5;
+    let offset = Vec2::new(wobble(0.0), wobble(10.0)) * MAX_SHAKE_OFFSET * shake;
+    let position = controller.position() + offset;
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+    transform.rotation = Quat::from_rotation_z(wobble(20.0) * MAX_SHAKE_ANGLE * shake);
+
+    let scale = 1.0 - feel.zoom;
+    if projection.scale != scale {
+        projection.scale = scale;
+    }
+}
+
+// STARTS EVERY FLASH SENT THIS FRAME, THE LAST ONE WINS, AND FADES THE CURRENT ONE OUT
+pub fn fade_screen_flash(
+    mut flash_events: EventReader<ScreenFlash>,
+    mut overlay_query: Query<(&mut FlashOverlay, &mut BackgroundColor)>,
+    time: Res<Time>,
+) {
+    let Ok((mut overlay, mut background)) = overlay_query.get_single_mut() else {
+        return;
+    };
+    if let Some(flash) = flash_events.iter().last() {
+        overlay.color = flash.color;
+        overlay.remaining = FLASH_TIME;
+    }
+    if overlay.remaining <= 0.0 && background.0.a() == 0.0 {
+        return;
+    }
+
+    overlay.remaining = (overlay.remaining - time.delta_seconds()).max(0.0);
+    let alpha = overlay.color.a() * overlay.remaining / FLASH_TIME;
+    background.0 = overlay.color.with_a(alpha);
+}
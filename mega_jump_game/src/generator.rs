@@ -0,0 +1,391 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::behavior::PlatformBehavior;
+use crate::coin::{spawn_coin, Coin, CoinPickup, COIN_RADIUS};
+use crate::hazard::{default_enemy_size, spawn_enemy, EnemyDesc, Hazard, SpikeDesc};
+use crate::launcher::{Launcher, SpringDesc, SPRING_SIZE, SPRING_STRENGTH, TRAMPOLINE_STRENGTH};
+use crate::level::PlatformDesc;
+use crate::motion::PlatformPath;
+use crate::map::{spawn_platform, CurrentLevel, MovingPlatform, Platform, DEFAULT_EASE_DISTANCE};
+use crate::powerup::{spawn_power_up, PowerUp, PowerUpDesc, PowerUpKind, POWER_UP_RADIUS};
+
+// HEIGHT OF ONE GENERATED CHUNK, EACH CHUNK GETS ITS OWN RNG SO A SEED ALWAYS BUILDS THE SAME CLIMB
+pub const CHUNK_HEIGHT: f32 = 2000.0;
+// HOW FAR ABOVE THE TOP OF THE SCREEN PLATFORMS ARE GENERATED
+pub const SPAWN_AHEAD: f32 = 1500.0;
+// HOW FAR BELOW THE BOTTOM OF THE SCREEN PLATFORMS ARE DESPAWNED
+pub const DESPAWN_BELOW: f32 = 1500.0;
+// HEIGHT ABOVE THE FIRST GENERATED CHUNK AT WHICH THE DIFFICULTY STOPS RAMPING
+pub const MAX_DIFFICULTY_HEIGHT: f32 = 12000.0;
+// ENVIRONMENT VARIABLE USED TO REPLAY A RUN WITH A FIXED SEED
+pub const SEED_ENV_VAR: &str = "MEGA_JUMP_SEED";
+
+const PLATFORM_HEIGHT: f32 = 30.0;
+const MIN_PLATFORM_WIDTH: f32 = 20.0;
+const MAX_PLATFORM_WIDTH: f32 = 200.0;
+const MIN_GAP: f32 = 250.0;
+const MAX_GAP: f32 = 500.0;
+const MIN_SPEED: f32 = 60.0;
+const MAX_SPEED: f32 = 150.0;
+const MIN_RANGE: f32 = 100.0;
+const MAX_RANGE: f32 = 400.0;
+const SIDE_MARGIN: f32 = 5.0;
+// DIFFICULTY FROM WHICH VERTICAL AND ORBITING PLATFORMS START TO SHOW UP, AND HOW OFTEN AT MOST
+const PATH_MOVER_DIFFICULTY: f32 = 0.5;
+const MAX_PATH_MOVER_CHANCE: f32 = 0.4;
+const MOVER_AMPLITUDE: f32 = 60.0;
+const MOVER_PERIOD: f32 = 3.0;
+// HOW OFTEN A PLATFORM CRUMBLES, VANISHES OR BLINKS AT FULL DIFFICULTY
+const MAX_SPECIAL_CHANCE: f32 = 0.35;
+const CRUMBLE_DELAY: f32 = 1.0;
+const TIMED_ON: f32 = 2.0;
+const TIMED_OFF: f32 = 1.0;
+// HOW OFTEN AN ORDINARY SLIDING PLATFORM CARRIES A SPRING PAD, OR IS A TRAMPOLINE INSTEAD
+const SPRING_CHANCE: f64 = 0.1;
+const TRAMPOLINE_CHANCE: f64 = 0.05;
+// HOW OFTEN A PLATFORM GETS SPIKES, OR AN ENEMY PATROLS THE GAP ABOVE IT, AT FULL DIFFICULTY
+const MAX_SPIKE_CHANCE: f32 = 0.2;
+const MAX_ENEMY_CHANCE: f32 = 0.15;
+const MIN_SPIKED_PLATFORM_WIDTH: f32 = 90.0;
+// HOW OFTEN A POWER UP FLOATS IN THE GAP ABOVE A PLATFORM THAT HAS NO ENEMY
+const POWER_UP_CHANCE: f64 = 0.06;
+// HOW OFTEN A LINE OF COINS RUNS UP THE GAP ABOVE A PLATFORM, AND HOW MANY COINS IT HOLDS
+const COIN_LINE_CHANCE: f64 = 0.3;
+const COINS_PER_LINE: usize = 3;
+
+// STATE OF THE ENDLESS CLIMB ABOVE THE HAND MADE LEVEL
+#[derive(Resource)]
+pub struct PlatformGenerator {
+    pub seed: u64,
+    // WORLD Y OF THE FIRST GENERATED CHUNK, SET ONCE THE LEVEL HAS BEEN SPAWNED, DIFFICULTY IS MEASURED FROM HERE
+    base_y: Option<f32>,
+    next_chunk: u64,
+    next_y: f32,
+}
+
+impl PlatformGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            base_y: None,
+            next_chunk: 0,
+            next_y: 0.0,
+        }
+    }
+
+    // READS THE SEED FROM MEGA_JUMP_SEED, OR PICKS ONE FROM THE CLOCK
+    pub fn from_env() -> Self {
+        let seed = std::env::var(SEED_ENV_VAR)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_secs())
+                    .unwrap_or_default()
+            });
+        info!("platform seed: {seed} (set {SEED_ENV_VAR} to replay)");
+        Self::new(seed)
+    }
+
+    // STARTS GENERATING AT base_y, THE FIRST GENERATED PLATFORM IS THE EASIEST ONE
+    fn start_at(&mut self, base_y: f32) {
+        self.base_y = Some(base_y);
+        self.next_y = base_y;
+    }
+
+    // HOW FAR THE NEXT PLATFORM IS ABOVE THE FIRST GENERATED ONE
+    fn height(&self) -> f32 {
+        self.next_y - self.base_y.unwrap_or(self.next_y)
+    }
+
+    fn chunk_rng(&self, chunk: u64) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ chunk.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+}
+
+// STARTS A FRESH CLIMB FOR A NEW RUN, WITH A NEW SEED UNLESS MEGA_JUMP_SEED IS SET
+pub fn reset_generator(mut generator: ResMut<PlatformGenerator>) {
+    *generator = PlatformGenerator::from_env();
+}
+
+// 0.0 AT THE FIRST GENERATED PLATFORM UP TO 1.0 AT MAX_DIFFICULTY_HEIGHT
+pub fn difficulty(height: f32) -> f32 {
+    (height / MAX_DIFFICULTY_HEIGHT).clamp(0.0, 1.0)
+}
+
+// BUILDS ONE PLATFORM, NARROWER, FURTHER APART AND FASTER THE HIGHER IT IS, MAYBE WITH AN ENEMY ABOVE IT
+fn generate_platform(
+    rng: &mut StdRng,
+    window_width: f32,
+    difficulty: f32,
+) -> (PlatformDesc, Option<EnemyDesc>, f32) {
+    let width = MAX_PLATFORM_WIDTH + (MIN_PLATFORM_WIDTH - MAX_PLATFORM_WIDTH) * difficulty;
+    let max_gap = MIN_GAP + (MAX_GAP - MIN_GAP) * difficulty;
+    let mut gap = rng.gen_range(max_gap * 0.8..=max_gap);
+    let speed = MIN_SPEED + (MAX_SPEED - MIN_SPEED) * difficulty;
+    let range = MIN_RANGE + (MAX_RANGE - MIN_RANGE) * difficulty;
+
+    let lowest_x = SIDE_MARGIN + width / 2.0;
+    let highest_x = (window_width - SIDE_MARGIN - width / 2.0).max(lowest_x);
+    let x = rng.gen_range(lowest_x..=highest_x);
+    let min_x = (x - range / 2.0).max(lowest_x);
+    let max_x = (x + range / 2.0).min(highest_x);
+
+    let mut platform = PlatformDesc {
+        offset: Vec2::new(x, 0.0),
+        size: Vec2::new(width, PLATFORM_HEIGHT),
+        color: Color::ORANGE_RED,
+        biome_tint: true,
+        moving: None,
+        path: None,
+        behavior: None,
+        trampoline: None,
+        spring: None,
+        spikes: None,
+    };
+
+    if rng.gen_bool((difficulty * MAX_SPECIAL_CHANCE) as f64) {
+        platform.behavior = Some(match rng.gen_range(0..3) {
+            0 => PlatformBehavior::Crumbling {
+                delay: CRUMBLE_DELAY,
+            },
+            1 => PlatformBehavior::Vanishing,
+            _ => PlatformBehavior::Timed {
+                on: TIMED_ON,
+                off: TIMED_OFF,
+                offset: rng.gen_range(0.0..TIMED_ON + TIMED_OFF),
+            },
+        });
+    }
+
+    // spikes only ever cover one side so there is always somewhere safe to land
+    if platform.behavior.is_none()
+        && width >= MIN_SPIKED_PLATFORM_WIDTH
+        && rng.gen_bool((difficulty * MAX_SPIKE_CHANCE) as f64)
+    {
+        let side = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+        platform.spikes = Some(SpikeDesc {
+            offset_x: side * width / 4.0,
+            width: width / 3.0,
+        });
+    }
+
+    // higher up, some platforms bob or orbit instead of sliding, with the gap shrunk so they stay reachable
+    let path_chance = ((difficulty - PATH_MOVER_DIFFICULTY) / (1.0 - PATH_MOVER_DIFFICULTY)).max(0.0)
+        * MAX_PATH_MOVER_CHANCE;
+    if rng.gen_bool(path_chance as f64) {
+        let period = MOVER_PERIOD * rng.gen_range(0.8..=1.2);
+        platform.path = Some(if rng.gen_bool(0.5) {
+            PlatformPath::Vertical {
+                amplitude: MOVER_AMPLITUDE,
+                period,
+            }
+        } else {
+            let radius_x = (range / 2.0).min((highest_x - lowest_x) / 2.0);
+            platform.offset.x = x.clamp(lowest_x + radius_x, highest_x - radius_x);
+            PlatformPath::Orbit {
+                radii: Vec2::new(radius_x, MOVER_AMPLITUDE),
+                period,
+                phase: rng.gen_range(0.0..1.0),
+            }
+        });
+        gap -= MOVER_AMPLITUDE;
+    } else {
+        if platform.behavior.is_none() && platform.spikes.is_none() {
+            if width >= SPRING_SIZE.x && rng.gen_bool(SPRING_CHANCE) {
+                platform.spring = Some(SpringDesc {
+                    offset_x: 0.0,
+                    strength: SPRING_STRENGTH,
+                });
+            } else if rng.gen_bool(TRAMPOLINE_CHANCE) {
+                platform.trampoline = Some(Launcher {
+                    strength: TRAMPOLINE_STRENGTH,
+                });
+            }
+        }
+        platform.moving = Some(MovingPlatform {
+            direction: if rng.gen_bool(0.5) { 1 } else { -1 },
+            min_x,
+            max_x,
+            speed,
+            ease_distance: DEFAULT_EASE_DISTANCE,
+        });
+    }
+
+    // enemies patrol halfway up the gap, across the whole window
+    let enemy = rng.gen_bool((difficulty * MAX_ENEMY_CHANCE) as f64).then(|| {
+        let size = default_enemy_size();
+        EnemyDesc {
+            offset: Vec2::new(window_width / 2.0, gap / 2.0),
+            size,
+            moving: MovingPlatform {
+                direction: if rng.gen_bool(0.5) { 1 } else { -1 },
+                min_x: size.x / 2.0,
+                max_x: window_width - size.x / 2.0,
+                speed,
+                ease_distance: DEFAULT_EASE_DISTANCE,
+            },
+        }
+    });
+    (platform, enemy, gap)
+}
+
+// MAYBE PLACES A RANDOM POWER UP SOMEWHERE HALFWAY UP THE GAP ABOVE A PLATFORM
+fn generate_power_up(rng: &mut StdRng, window_width: f32, gap: f32) -> Option<PowerUpDesc> {
+    rng.gen_bool(POWER_UP_CHANCE).then(|| PowerUpDesc {
+        offset: Vec2::new(rng.gen_range(POWER_UP_RADIUS..window_width - POWER_UP_RADIUS), gap / 2.0),
+        kind: PowerUpKind::ALL[rng.gen_range(0..PowerUpKind::ALL.len())],
+    })
+}
+
+// MAYBE PLACES A VERTICAL LINE OF COINS EVENLY SPACED UP THE GAP ABOVE A PLATFORM
+fn generate_coins(rng: &mut StdRng, window_width: f32, gap: f32) -> Vec<Vec2> {
+    if !rng.gen_bool(COIN_LINE_CHANCE) {
+        return Vec::new();
+    }
+    let x = rng.gen_range(COIN_RADIUS..window_width - COIN_RADIUS);
+    let spacing = gap / (COINS_PER_LINE + 1) as f32;
+    (1..=COINS_PER_LINE).map(|i| Vec2::new(x, spacing * i as f32)).collect()
+}
+
+// SPAWNS NEW CHUNKS OF PLATFORMS ABOVE THE CAMERA AS THE PLAYER CLIMBS
+pub fn generate_platforms(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    current_level: Res<CurrentLevel>,
+    mut generator: ResMut<PlatformGenerator>,
+) {
+    let window: &Window = window_query.get_single().unwrap();
+
+    let base_y = match generator.base_y {
+        Some(base_y) => base_y,
+        None => {
+            if !current_level.spawned {
+                return;
+            }
+            let base_y = current_level.top + MIN_GAP;
+            generator.start_at(base_y);
+            base_y
+        }
+    };
+
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let spawn_limit = camera_transform.translation.y + window.height() / 2.0 + SPAWN_AHEAD;
+
+    while base_y + generator.next_chunk as f32 * CHUNK_HEIGHT < spawn_limit {
+        let chunk = generator.next_chunk;
+        let chunk_end = base_y + (chunk + 1) as f32 * CHUNK_HEIGHT;
+        let mut rng = generator.chunk_rng(chunk);
+
+        while generator.next_y < chunk_end {
+            let height = generator.height();
+            let (platform, enemy, gap) = generate_platform(&mut rng, window.width(), difficulty(height));
+            let origin = Vec2::new(0.0, generator.next_y);
+            spawn_platform(&mut commands, &mut meshes, &mut materials, origin, &platform);
+            if let Some(enemy) = enemy {
+                spawn_enemy(&mut commands, &mut meshes, &mut materials, origin, &enemy);
+            } else if let Some(power_up) = generate_power_up(&mut rng, window.width(), gap) {
+                spawn_power_up(&mut commands, &mut meshes, &mut materials, origin, &power_up);
+            }
+            for offset in generate_coins(&mut rng, window.width(), gap) {
+                spawn_coin(&mut commands, &mut meshes, &mut materials, origin, offset);
+            }
+            generator.next_y += gap;
+        }
+        generator.next_chunk += 1;
+    }
+}
+
+// TOP LEVEL PLATFORMS, HAZARDS AND PICKUPS, CHILDREN ARE DESPAWNED ALONG WITH THEIR PARENT
+type Scrolling = (
+    Or<(With<Platform>, With<Hazard>, With<PowerUp>, With<Coin>, With<CoinPickup>)>,
+    Without<Parent>,
+);
+
+// DESPAWNS PLATFORMS, HAZARDS AND PICKUPS THAT HAVE FALLEN FAR BELOW THE CAMERA
+pub fn despawn_platforms_below(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    platform_query: Query<(Entity, &Transform), Scrolling>,
+) {
+    let window: &Window = window_query.get_single().unwrap();
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let despawn_limit = camera_transform.translation.y - window.height() / 2.0 - DESPAWN_BELOW;
+
+    for (entity, transform) in platform_query.iter() {
+        if transform.translation.y < despawn_limit {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW_WIDTH: f32 = 1280.0;
+
+    #[test]
+    fn difficulty_starts_at_the_first_generated_platform() {
+        let mut generator = PlatformGenerator::new(1);
+        generator.start_at(12000.0 + MIN_GAP);
+        assert_eq!(generator.height(), 0.0);
+        assert_eq!(difficulty(generator.height()), 0.0);
+        assert_eq!(difficulty(MAX_DIFFICULTY_HEIGHT), 1.0);
+        assert_eq!(difficulty(MAX_DIFFICULTY_HEIGHT * 2.0), 1.0);
+    }
+
+    #[test]
+    fn platforms_ramp_from_the_first_chunk_to_the_last() {
+        let generator = PlatformGenerator::new(1);
+        let last_chunk = (MAX_DIFFICULTY_HEIGHT / CHUNK_HEIGHT) as u64 - 1;
+
+        let mut rng = generator.chunk_rng(0);
+        let (first, _, first_gap) = generate_platform(&mut rng, WINDOW_WIDTH, difficulty(0.0));
+        assert_eq!(first.size.x, MAX_PLATFORM_WIDTH);
+        assert!(first_gap <= MIN_GAP);
+
+        let mut rng = generator.chunk_rng(last_chunk);
+        let (last, _, last_gap) = generate_platform(&mut rng, WINDOW_WIDTH, difficulty(MAX_DIFFICULTY_HEIGHT));
+        assert_eq!(last.size.x, MIN_PLATFORM_WIDTH);
+        assert!(last_gap > MIN_GAP);
+    }
+
+    #[test]
+    fn low_platforms_roll_springs_and_trampolines() {
+        let generator = PlatformGenerator::new(1);
+        let platforms: Vec<PlatformDesc> = (0..200)
+            .map(|chunk| generate_platform(&mut generator.chunk_rng(chunk), WINDOW_WIDTH, difficulty(2000.0)).0)
+            .collect();
+        assert!(platforms.iter().any(|platform| platform.spring.is_some()));
+        assert!(platforms.iter().any(|platform| platform.trampoline.is_some()));
+        assert!(platforms
+            .iter()
+            .all(|platform| platform.spring.is_none() || platform.trampoline.is_none()));
+    }
+
+    #[test]
+    fn mid_platforms_roll_spikes_and_enemies() {
+        let generator = PlatformGenerator::new(1);
+        let rolls: Vec<(PlatformDesc, Option<EnemyDesc>, f32)> = (0..200)
+            .map(|chunk| generate_platform(&mut generator.chunk_rng(chunk), WINDOW_WIDTH, difficulty(5000.0)))
+            .collect();
+        assert!(rolls.iter().any(|(platform, _, _)| platform.spikes.is_some()));
+        assert!(rolls.iter().any(|(_, enemy, _)| enemy.is_some()));
+        assert!(rolls
+            .iter()
+            .all(|(platform, _, _)| platform.spikes.is_none() || platform.size.x >= MIN_SPIKED_PLATFORM_WIDTH));
+    }
+}
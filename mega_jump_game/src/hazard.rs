@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy::window::PrimaryWindow;
+use bevy_rapier2d::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::generator::{difficulty, PlatformGenerator};
+use crate::map::MovingPlatform;
+use crate::score::Score;
+use crate::Player;
+
+const SPIKE_SIZE: Vec2 = Vec2::new(16.0, 16.0);
+const SPIKE_COLOR: Color = Color::GRAY;
+const ENEMY_COLOR: Color = Color::CRIMSON;
+const PROJECTILE_COLOR: Color = Color::DARK_GRAY;
+const PROJECTILE_RADIUS: f32 = 14.0;
+const PROJECTILE_SPEED: f32 = 350.0;
+// PROJECTILES START FALLING ONCE THE CLIMB IS THIS DIFFICULT, THEN GET MORE FREQUENT UP TO MAX DIFFICULTY
+const PROJECTILE_START_DIFFICULTY: f32 = 0.3;
+const SLOWEST_PROJECTILE_INTERVAL: f32 = 6.0;
+const FASTEST_PROJECTILE_INTERVAL: f32 = 2.0;
+
+// ANYTHING THAT HURTS THE PLAYER ON CONTACT
+#[derive(Component)]
+pub struct Hazard;
+
+// FALLS STRAIGHT DOWN UNTIL IT LEAVES THE BOTTOM OF THE SCREEN
+#[derive(Component)]
+pub struct Projectile {
+    pub speed: f32,
+}
+
+// SENT WHEN THE PLAYER TOUCHES A HAZARD, source IS WHERE THE HIT CAME FROM
+pub struct PlayerHurt {
+    pub source: Vec2,
+}
+
+// A ROW OF SPIKES ON TOP OF A PLATFORM, offset_x IS MEASURED FROM THE PLATFORM'S CENTRE
+#[derive(Deserialize, Clone)]
+pub struct SpikeDesc {
+    #[serde(default)]
+    pub offset_x: f32,
+    pub width: f32,
+}
+
+// AN ENEMY PATROLLING BACK AND FORTH LIKE A MovingPlatform, OFFSET IS FROM THE CENTRE OF THE WINDOW
+#[derive(Deserialize, Clone)]
+pub struct EnemyDesc {
+    pub offset: Vec2,
+    #[serde(default = "default_enemy_size")]
+    pub size: Vec2,
+    pub moving: MovingPlatform,
+}
+
+pub fn default_enemy_size() -> Vec2 {
+    Vec2::new(40.0, 40.0)
+}
+
+// DROPS PROJECTILES FROM ABOVE THE SCREEN, SEEDED FROM THE PLATFORM SEED SO RUNS REPLAY THE SAME
+#[derive(Resource, Default)]
+pub struct ProjectileSpawner {
+    rng: Option<StdRng>,
+    cooldown: f32,
+}
+
+pub fn reset_projectile_spawner(mut spawner: ResMut<ProjectileSpawner>) {
+    *spawner = ProjectileSpawner::default();
+}
+
+// SPAWNS A ROW OF SPIKES AS CHILDREN OF A PLATFORM SO THEY RIDE ALONG WITH IT
+pub fn spawn_spikes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    platform: Entity,
+    platform_height: f32,
+    spikes: &SpikeDesc,
+) {
+    let count = (spikes.width / SPIKE_SIZE.x).floor().max(1.0);
+    let width = count * SPIKE_SIZE.x;
+    let y = (platform_height + SPIKE_SIZE.y) / 2.0;
+    let mesh = meshes.add(Mesh::from(shape::RegularPolygon::new(SPIKE_SIZE.x / 2.0, 3)));
+    let material = materials.add(ColorMaterial::from(SPIKE_COLOR));
+
+    let spike_row = commands
+        .spawn((
+            Hazard,
+            Collider::cuboid(width / 2.0, SPIKE_SIZE.y / 2.0),
+            Sensor,
+            TransformBundle::from(Transform::from_xyz(spikes.offset_x, y, 0.1)),
+            VisibilityBundle::default(),
+        ))
+        .with_children(|parent| {
+            for i in 0..count as usize {
+                parent.spawn(MaterialMesh2dBundle {
+                    mesh: mesh.clone().into(),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(-width / 2.0 + SPIKE_SIZE.x * (i as f32 + 0.5), 0.0, 0.0),
+                    ..default()
+                });
+            }
+        })
+        .id();
+    commands.entity(platform).add_child(spike_row);
+}
+
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    origin: Vec2,
+    enemy: &EnemyDesc,
+) -> Entity {
+    let position = origin + enemy.offset;
+    commands
+        .spawn((
+            Hazard,
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(enemy.size.x / 2.0, enemy.size.y / 2.0),
+            Sensor,
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Mesh::from(shape::Quad::new(enemy.size))).into(),
+                material: materials.add(ColorMaterial::from(ENEMY_COLOR)),
+                transform: Transform::from_xyz(position.x, position.y, 0.2),
+                ..default()
+            },
+            enemy.moving.clone(),
+        ))
+        .id()
+}
+
+// DROPS PROJECTILES MORE AND MORE OFTEN THE HIGHER THE PLAYER CLIMBS
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_projectiles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut spawner: ResMut<ProjectileSpawner>,
+    generator: Res<PlatformGenerator>,
+    score: Res<Score>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    time: Res<Time>,
+) {
+    let difficulty = difficulty(score.current);
+    if difficulty < PROJECTILE_START_DIFFICULTY {
+        return;
+    }
+    spawner.cooldown -= time.delta_seconds();
+    if spawner.cooldown > 0.0 {
+        return;
+    }
+    let window: &Window = window_query.get_single().unwrap();
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    let ramp = (difficulty - PROJECTILE_START_DIFFICULTY) / (1.0 - PROJECTILE_START_DIFFICULTY);
+    spawner.cooldown = SLOWEST_PROJECTILE_INTERVAL + (FASTEST_PROJECTILE_INTERVAL - SLOWEST_PROJECTILE_INTERVAL) * ramp;
+    let rng = spawner
+        .rng
+        .get_or_insert_with(|| StdRng::seed_from_u64(generator.seed.wrapping_add(1)));
+    let x = rng.gen_range(PROJECTILE_RADIUS..window.width() - PROJECTILE_RADIUS);
+    let y = camera_transform.translation.y + window.height() / 2.0 + PROJECTILE_RADIUS;
+
+    commands.spawn((
+        Hazard,
+        Projectile {
+            speed: PROJECTILE_SPEED,
+        },
+        RigidBody::KinematicPositionBased,
+        Collider::ball(PROJECTILE_RADIUS),
+        Sensor,
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Mesh::from(shape::Circle::new(PROJECTILE_RADIUS))).into(),
+            material: materials.add(ColorMaterial::from(PROJECTILE_COLOR)),
+            transform: Transform::from_xyz(x, y, 0.2),
+            ..default()
+        },
+    ));
+}
+
+// MOVES PROJECTILES DOWN AND REMOVES THEM ONCE THEY ARE OFF THE BOTTOM OF THE SCREEN
+pub fn move_projectiles(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Transform, &Projectile)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, (With<Camera2d>, Without<Projectile>)>,
+    time: Res<Time>,
+) {
+    let window: &Window = window_query.get_single().unwrap();
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let bottom = camera_transform.translation.y - window.height() / 2.0 - PROJECTILE_RADIUS;
+
+    for (entity, mut transform, projectile) in projectile_query.iter_mut() {
+        transform.translation.y -= projectile.speed * time.delta_seconds();
+        if transform.translation.y < bottom {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// SENDS A PlayerHurt FOR EVERY HAZARD THE PLAYER IS TOUCHING
+pub fn detect_hazard_hits(
+    rapier_context: Res<RapierContext>,
+    player_query: Query<Entity, With<Player>>,
+    hazard_query: Query<&GlobalTransform, With<Hazard>>,
+    mut hurt_events: EventWriter<PlayerHurt>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for (collider1, collider2, intersecting) in rapier_context.intersections_with(player_entity) {
+        if !intersecting {
+            continue;
+        }
+        let other = if collider1 == player_entity {
+            collider2
+        } else {
+            collider1
+        };
+        if let Ok(hazard_transform) = hazard_query.get(other) {
+            hurt_events.send(PlayerHurt {
+                source: hazard_transform.translation().truncate(),
+            });
+        }
+    }
+}
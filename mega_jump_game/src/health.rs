@@ -0,0 +1,285 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::audio::SoundEffect;
+use crate::behavior::SpecialPlatform;
+use crate::camera::SnapCamera;
+use crate::death::HighestPlatform;
+use crate::feel::{CameraShake, ScreenFlash};
+use crate::hazard::PlayerHurt;
+use crate::map::Platform;
+use crate::powerup::Shield;
+use crate::state::GameState;
+use crate::{Jump, Player, PlayerState};
+
+pub const MAX_HEALTH: u32 = 3;
+pub const MAX_LIVES: u32 = 3;
+// SECONDS AFTER A HIT OR A RESPAWN DURING WHICH HAZARDS CAN'T HURT THE PLAYER
+const INVULNERABILITY_TIME: f32 = 1.5;
+const BLINK_INTERVAL: f32 = 0.1;
+// VELOCITY THE PLAYER IS THROWN AWAY FROM A HAZARD WITH, AND HOW LONG THEY CAN'T STEER AFTERWARDS
+const KNOCKBACK: Vec2 = Vec2::new(400.0, 600.0);
+const KNOCKBACK_TIME: f32 = 0.3;
+// HOW HARD A HIT, A BROKEN SHIELD AND A LOST LIFE SHAKE THE CAMERA, AND WHAT THE FIRST TWO FLASH THE SCREEN WITH
+const HURT_TRAUMA: f32 = 0.5;
+const SHIELD_TRAUMA: f32 = 0.3;
+const LIFE_LOST_TRAUMA: f32 = 0.7;
+const HURT_FLASH: Color = Color::rgba(1.0, 0.0, 0.0, 0.35);
+const SHIELD_FLASH: Color = Color::rgba(0.0, 1.0, 1.0, 0.3);
+const LIFE_ICON: &str = "sprites/Adventurer/Poses/adventurer_stand.png";
+const LIFE_ICON_SIZE: f32 = 40.0;
+const HEALTH_PIP_SIZE: f32 = 16.0;
+const HEALTH_COLOR: Color = Color::CRIMSON;
+
+// HITS LEFT ON THE CURRENT LIFE AND LIVES LEFT IN THE RUN
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub lives: u32,
+    pub invulnerable: f32, // seconds left before the player can be hurt again
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            current: MAX_HEALTH,
+            lives: MAX_LIVES,
+            invulnerable: 0.0,
+        }
+    }
+}
+
+// SENT WHEN THE PLAYER RUNS OUT OF HEALTH OR FALLS OFF THE CLIMB
+pub struct LifeLost;
+
+// THE LAST ORDINARY PLATFORM THE PLAYER STOOD ON AND WHERE ON IT, SO A LOST LIFE RESPAWNS THERE
+#[derive(Resource, Default)]
+pub struct SafePlatform {
+    entity: Option<Entity>,
+    offset: Vec2,
+    position: Option<Vec2>,
+}
+
+pub fn reset_safe_platform(mut safe_platform: ResMut<SafePlatform>) {
+    *safe_platform = SafePlatform::default();
+}
+
+// ONE PLAYER ICON PER LIFE IN THE TOP RIGHT CORNER
+#[derive(Component)]
+pub struct LifeIcon(u32);
+
+// ONE RED PIP PER HIT LEFT UNDER THE LIFE ICONS
+#[derive(Component)]
+pub struct HealthPip(u32);
+
+// PLATFORMS THAT STAY PUT UNDER THE PLAYER FOR AS LONG AS THEY ARE ON SCREEN
+type SafeGround = (With<Platform>, Without<SpecialPlatform>, Without<Player>);
+
+// REMEMBERS THE PLATFORM UNDER THE PLAYER UNLESS IT IS ABOUT TO CRUMBLE, VANISH OR BLINK AWAY
+pub fn track_safe_platform(
+    player_query: Query<(&Player, &Transform, &Health)>,
+    platform_query: Query<&Transform, SafeGround>,
+    mut safe_platform: ResMut<SafePlatform>,
+) {
+    let Ok((player, player_transform, health)) = player_query.get_single() else {
+        return;
+    };
+    // standing next to the spikes that just hurt the player is not safe
+    if health.invulnerable > 0.0 {
+        return;
+    }
+    let Some(platform) = player.grounded_on else {
+        return;
+    };
+    let Ok(platform_transform) = platform_query.get(platform) else {
+        return;
+    };
+
+    safe_platform.entity = Some(platform);
+    safe_platform.offset = (player_transform.translation - platform_transform.translation).truncate();
+    safe_platform.position = Some(player_transform.translation.truncate());
+}
+
+// TAKES A HIT OFF THE PLAYER'S HEALTH AND KNOCKS THEM AWAY FROM WHATEVER HURT THEM, A SHIELD TAKES THE HIT INSTEAD
+#[allow(clippy::too_many_arguments)]
+pub fn damage_player(
+    mut commands: Commands,
+    mut hurt_events: EventReader<PlayerHurt>,
+    mut player_query: Query<(Entity, &mut Player, &mut Health, &Transform, &mut Velocity)>,
+    shield_query: Query<(), With<Shield>>,
+    mut life_lost_events: EventWriter<LifeLost>,
+    mut sound_events: EventWriter<SoundEffect>,
+    mut shake_events: EventWriter<CameraShake>,
+    mut flash_events: EventWriter<ScreenFlash>,
+) {
+    let Some(hurt) = hurt_events.iter().last() else {
+        return;
+    };
+    let Ok((entity, mut player, mut health, transform, mut velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+    if health.invulnerable > 0.0 {
+        return;
+    }
+    if shield_query.contains(entity) {
+        commands.entity(entity).remove::<Shield>();
+        health.invulnerable = INVULNERABILITY_TIME;
+        sound_events.send(SoundEffect::ShieldBreak);
+        shake_events.send(CameraShake { trauma: SHIELD_TRAUMA });
+        flash_events.send(ScreenFlash { color: SHIELD_FLASH });
+        return;
+    }
+
+    health.current = health.current.saturating_sub(1);
+    health.invulnerable = INVULNERABILITY_TIME;
+    if health.current == 0 {
+        life_lost_events.send(LifeLost);
+        return;
+    }
+
+    let direction = if transform.translation.x < hurt.source.x { -1.0 } else { 1.0 };
+    velocity.linvel = Vec2::new(direction * KNOCKBACK.x, KNOCKBACK.y);
+    player.knockback = KNOCKBACK_TIME;
+    player.state = PlayerState::Hurt;
+    commands.entity(entity).remove::<Jump>();
+    sound_events.send(SoundEffect::Hurt);
+    shake_events.send(CameraShake { trauma: HURT_TRAUMA });
+    flash_events.send(ScreenFlash { color: HURT_FLASH });
+}
+
+// TAKES A LIFE AND PUTS THE PLAYER BACK ON THEIR LAST SAFE PLATFORM, OR ENDS THE RUN ON THE LAST ONE
+#[allow(clippy::too_many_arguments)]
+pub fn lose_life(
+    mut commands: Commands,
+    mut life_lost_events: EventReader<LifeLost>,
+    mut player_query: Query<(Entity, &mut Player, &mut Health, &mut Transform, &mut Velocity)>,
+    platform_query: Query<&Transform, (With<Platform>, Without<Health>)>,
+    safe_platform: Res<SafePlatform>,
+    mut highest_platform: ResMut<HighestPlatform>,
+    mut sound_events: EventWriter<SoundEffect>,
+    mut snap_events: EventWriter<SnapCamera>,
+    mut shake_events: EventWriter<CameraShake>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if life_lost_events.iter().count() == 0 {
+        return;
+    }
+    let Ok((entity, mut player, mut health, mut transform, mut velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    sound_events.send(SoundEffect::Death);
+    shake_events.send(CameraShake { trauma: LIFE_LOST_TRAUMA });
+    health.lives = health.lives.saturating_sub(1);
+    if health.lives == 0 {
+        player.state = PlayerState::Hurt;
+        next_state.set(GameState::GameOver);
+        return;
+    }
+
+    // the platform may have moved since, so follow it, falling back to where it was last seen
+    let position = safe_platform
+        .entity
+        .and_then(|platform| platform_query.get(platform).ok())
+        .map(|platform_transform| platform_transform.translation.truncate() + safe_platform.offset)
+        .or(safe_platform.position);
+    if let Some(position) = position {
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+        highest_platform.0 = Some(position.y - safe_platform.offset.y);
+    }
+    snap_events.send(SnapCamera);
+    velocity.linvel = Vec2::ZERO;
+    commands.entity(entity).remove::<Jump>();
+    health.current = MAX_HEALTH;
+    health.invulnerable = INVULNERABILITY_TIME;
+}
+
+// COUNTS DOWN INVULNERABILITY AND BLINKS THE PLAYER SPRITE WHILE IT LASTS
+pub fn blink_invulnerable(mut player_query: Query<(&mut Health, &mut Visibility)>, time: Res<Time>) {
+    let Ok((mut health, mut visibility)) = player_query.get_single_mut() else {
+        return;
+    };
+    health.invulnerable = (health.invulnerable - time.delta_seconds()).max(0.0);
+    let hidden = health.invulnerable > 0.0 && (health.invulnerable / BLINK_INTERVAL) as u32 % 2 == 1;
+    *visibility = if hidden { Visibility::Hidden } else { Visibility::Inherited };
+}
+
+// ROW OF LIFE ICONS WITH A ROW OF HEALTH PIPS UNDER IT IN THE TOP RIGHT CORNER
+pub fn spawn_health_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let icon = asset_server.load(LIFE_ICON);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle::default())
+                .with_children(|row| {
+                    for i in 0..MAX_LIVES {
+                        row.spawn((
+                            ImageBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(LIFE_ICON_SIZE), Val::Px(LIFE_ICON_SIZE)),
+                                    margin: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                image: icon.clone().into(),
+                                visibility: Visibility::Hidden,
+                                ..default()
+                            },
+                            LifeIcon(i),
+                        ));
+                    }
+                });
+            parent
+                .spawn(NodeBundle::default())
+                .with_children(|row| {
+                    for i in 0..MAX_HEALTH {
+                        row.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(HEALTH_PIP_SIZE), Val::Px(HEALTH_PIP_SIZE)),
+                                    margin: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: HEALTH_COLOR.into(),
+                                visibility: Visibility::Hidden,
+                                ..default()
+                            },
+                            HealthPip(i),
+                        ));
+                    }
+                });
+        });
+}
+
+// SHOWS AS MANY ICONS AND PIPS AS THE PLAYER HAS LIVES AND HEALTH LEFT, NONE OUTSIDE A RUN
+pub fn update_health_hud(
+    health_query: Query<&Health>,
+    mut icon_query: Query<(&LifeIcon, &mut Visibility), Without<HealthPip>>,
+    mut pip_query: Query<(&HealthPip, &mut Visibility), Without<LifeIcon>>,
+) {
+    let (lives, current) = health_query
+        .get_single()
+        .map_or((0, 0), |health| (health.lives, health.current));
+
+    for (icon, mut visibility) in icon_query.iter_mut() {
+        *visibility = if icon.0 < lives { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    for (pip, mut visibility) in pip_query.iter_mut() {
+        *visibility = if pip.0 < current { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
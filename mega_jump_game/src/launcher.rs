@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::audio::SoundEffect;
+use crate::feel::{CameraShake, ZoomPunch};
+use crate::{Jump, Player, JUMP_FORCE};
+
+pub const SPRING_STRENGTH: f32 = JUMP_FORCE * 2.0;
+pub const TRAMPOLINE_STRENGTH: f32 = JUMP_FORCE * 1.5;
+pub const SPRING_SIZE: Vec2 = Vec2::new(40.0, 12.0);
+const SPRING_COLOR: Color = Color::YELLOW;
+// A LAUNCH AT JUMP_FORCE WOULD ZOOM IN THIS MUCH, STRONGER LAUNCHERS PUNCH HARDER
+const ZOOM_PER_JUMP_FORCE: f32 = 0.06;
+const LAUNCH_TRAUMA: f32 = 0.25;
+pub const TRAMPOLINE_COLOR: Color = Color::LIME_GREEN;
+
+// ANYTHING THAT THROWS THE PLAYER UPWARDS WHEN THEY LAND ON IT, strength IS THE LAUNCH VELOCITY
+#[derive(Component, Deserialize, Clone, Copy)]
+pub struct Launcher {
+    #[serde(default = "default_trampoline_strength")]
+    pub strength: f32,
+}
+
+// A SPRING PAD SITTING ON TOP OF A PLATFORM, offset_x IS MEASURED FROM THE PLATFORM'S CENTRE
+#[derive(Deserialize, Clone)]
+pub struct SpringDesc {
+    #[serde(default)]
+    pub offset_x: f32,
+    #[serde(default = "default_spring_strength")]
+    pub strength: f32,
+}
+
+fn default_spring_strength() -> f32 {
+    SPRING_STRENGTH
+}
+
+fn default_trampoline_strength() -> f32 {
+    TRAMPOLINE_STRENGTH
+}
+
+// SPAWNS A SPRING PAD AS A CHILD OF ITS PLATFORM SO IT RIDES ALONG WHEN THE PLATFORM MOVES
+pub fn spawn_spring(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    platform: Entity,
+    platform_height: f32,
+    spring: &SpringDesc,
+) {
+    let spring_entity = commands
+        .spawn((
+            Launcher {
+                strength: spring.strength,
+            },
+            Collider::cuboid(SPRING_SIZE.x / 2.0, SPRING_SIZE.y / 2.0),
+            ActiveHooks::MODIFY_SOLVER_CONTACTS,
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Mesh::from(shape::Quad::new(SPRING_SIZE))).into(),
+                material: materials.add(ColorMaterial::from(SPRING_COLOR)),
+                transform: Transform::from_xyz(
+                    spring.offset_x,
+                    (platform_height + SPRING_SIZE.y) / 2.0,
+                    0.1,
+                ),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(platform).add_child(spring_entity);
+}
+
+// LAUNCHES THE PLAYER WHEN THEY LAND ON A SPRING OR TRAMPOLINE
+pub fn launch_player(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Player, &mut Velocity)>,
+    launcher_query: Query<&Launcher>,
+    mut sound_events: EventWriter<SoundEffect>,
+    mut shake_events: EventWriter<CameraShake>,
+    mut zoom_events: EventWriter<ZoomPunch>,
+) {
+    let Ok((entity, player, mut velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+    let Some(launcher) = player.grounded_on.and_then(|ground| launcher_query.get(ground).ok()) else {
+        return;
+    };
+    // only launch on the way down, the contact lingers for a frame after take off
+    if velocity.linvel.y > 0.0 {
+        return;
+    }
+
+    velocity.linvel.y = launcher.strength;
+    commands.entity(entity).insert(Jump {
+        held: true,
+        launched: true,
+    });
+    sound_events.send(SoundEffect::Jump);
+    shake_events.send(CameraShake { trauma: LAUNCH_TRAUMA });
+    zoom_events.send(ZoomPunch {
+        strength: ZOOM_PER_JUMP_FORCE * launcher.strength / JUMP_FORCE,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classic::GameMode;
+    use crate::move_player;
+
+    // RUNS ONE FRAME OF JUMPING WITH THE PLAYER FALLING ONTO A SPRING, RETURNS THEIR VELOCITY AND JUMP AFTERWARDS
+    fn land_on_spring(game_mode: GameMode, space_pressed: bool) -> (f32, bool) {
+        let mut app = App::new();
+        let mut keyboard = Input::<KeyCode>::default();
+        if space_pressed {
+            keyboard.press(KeyCode::Space);
+        }
+        app.insert_resource(keyboard)
+            .insert_resource(game_mode)
+            .init_resource::<Time>()
+            .add_event::<SoundEffect>()
+            .add_event::<CameraShake>()
+            .add_event::<ZoomPunch>()
+            .add_system(move_player)
+            .add_system(launch_player.after(move_player));
+        let spring = app.world.spawn(Launcher { strength: SPRING_STRENGTH }).id();
+        let player = app
+            .world
+            .spawn((
+                Player {
+                    grounded_on: Some(spring),
+                    ..default()
+                },
+                Velocity::linear(Vec2::new(0.0, -300.0)),
+            ))
+            .id();
+
+        app.update();
+        let velocity = app.world.get::<Velocity>(player).unwrap().linvel.y;
+        let launched = app.world.get::<Jump>(player).is_some_and(|jump| jump.launched);
+        (velocity, launched)
+    }
+
+    #[test]
+    fn springs_launch_in_both_modes() {
+        assert_eq!(land_on_spring(GameMode::Standard, false), (SPRING_STRENGTH, true));
+        assert_eq!(land_on_spring(GameMode::Classic, false), (SPRING_STRENGTH, true));
+    }
+
+    #[test]
+    fn a_buffered_jump_does_not_cancel_the_launch() {
+        assert_eq!(land_on_spring(GameMode::Standard, true), (SPRING_STRENGTH, true));
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+use crate::behavior::PlatformBehavior;
+use crate::hazard::{EnemyDesc, SpikeDesc};
+use crate::launcher::{Launcher, SpringDesc};
+use crate::map::MovingPlatform;
+use crate::motion::PlatformPath;
+use crate::powerup::PowerUpDesc;
+
+// ONE PLATFORM AS WRITTEN IN A LEVEL FILE, OFFSET IS MEASURED FROM THE CENTRE OF THE WINDOW
+#[derive(Deserialize, Clone)]
+pub struct PlatformDesc {
+    pub offset: Vec2,
+    pub size: Vec2,
+    pub color: Color,
+    // RE-TINTS THE PLATFORM TO THE BIOME IT IS IN, TURN OFF TO KEEP color ALL THE WAY UP
+    #[serde(default = "default_biome_tint")]
+    pub biome_tint: bool,
+    #[serde(default)]
+    pub moving: Option<MovingPlatform>,
+    #[serde(default)]
+    pub path: Option<PlatformPath>,
+    // A SPECIAL BEHAVIOR REPLACES color WITH THE BEHAVIOR'S OWN COLOR
+    #[serde(default)]
+    pub behavior: Option<PlatformBehavior>,
+    // MAKES THE WHOLE PLATFORM A TRAMPOLINE
+    #[serde(default)]
+    pub trampoline: Option<Launcher>,
+    #[serde(default)]
+    pub spring: Option<SpringDesc>,
+    #[serde(default)]
+    pub spikes: Option<SpikeDesc>,
+}
+
+fn default_biome_tint() -> bool {
+    true
+}
+
+// A WHOLE LEVEL LAYOUT LOADED FROM assets/levels/*.level.ron
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "6f0d3f4e-2b1a-4c55-9a61-0c7d2e8b5a13"]
+pub struct Level {
+    pub platforms: Vec<PlatformDesc>,
+    #[serde(default)]
+    pub enemies: Vec<EnemyDesc>,
+    #[serde(default)]
+    pub power_ups: Vec<PowerUpDesc>,
+    // OFFSETS OF SINGLE COINS, MEASURED LIKE PLATFORM OFFSETS
+    #[serde(default)]
+    pub coins: Vec<Vec2>,
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let level: Level = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desert() -> Level {
+        ron::from_str(include_str!("../assets/levels/desert.level.ron")).unwrap()
+    }
+
+    #[test]
+    fn desert_places_path_platforms() {
+        let level = desert();
+        let paths: Vec<&PlatformPath> = level.platforms.iter().filter_map(|platform| platform.path.as_ref()).collect();
+        assert!(paths.iter().any(|path| matches!(path, PlatformPath::Waypoints(points) if points.len() >= 2)));
+        assert!(paths.iter().any(|path| matches!(path, PlatformPath::Orbit { .. })));
+        assert!(level
+            .platforms
+            .iter()
+            .all(|platform| platform.path.is_none() || platform.moving.is_none()));
+    }
+
+    #[test]
+    fn desert_places_every_special_platform() {
+        let level = desert();
+        let behaviors: Vec<&PlatformBehavior> = level
+            .platforms
+            .iter()
+            .filter_map(|platform| platform.behavior.as_ref())
+            .collect();
+        assert!(behaviors.iter().any(|behavior| matches!(behavior, PlatformBehavior::Crumbling { .. })));
+        assert!(behaviors.iter().any(|behavior| matches!(behavior, PlatformBehavior::Vanishing)));
+        assert!(behaviors.iter().any(|behavior| matches!(behavior, PlatformBehavior::Timed { .. })));
+    }
+}
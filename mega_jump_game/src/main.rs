@@ -1,19 +1,72 @@
+mod animation;
+mod audio;
+mod behavior;
+mod biome;
+mod camera;
+mod classic;
+mod cloud;
+mod coin;
+mod death;
+mod feel;
+mod generator;
+mod hazard;
+mod health;
+mod launcher;
+mod level;
 mod map;
+mod motion;
+mod parallax;
+mod powerup;
+mod score;
+mod shop;
+mod state;
 
 use bevy::prelude::*;
 use bevy::window::{PresentMode, PrimaryWindow};
 use bevy_rapier2d::prelude::*;
+use animation::{animate_player, load_player_atlas, select_player_state, PlayerAnimation, PlayerAtlas};
+use audio::{apply_music_volume, play_music, play_sound_effects, volume_input, Music, SoundEffect, Volume};
+use behavior::update_special_platforms;
+use biome::apply_biome;
+use camera::{camera_follow, CameraController, SnapCamera};
+use classic::{GameMode, OneWayPlatformHooks};
+use cloud::{drift_clouds, maintain_cloud_count, CloudCover};
+use coin::{animate_coin_pickups, collect_coins};
+use death::{check_fall_death, reset_highest_platform, track_highest_platform, HighestPlatform};
+use feel::{apply_camera_feel, fade_screen_flash, spawn_flash_overlay, CameraFeel, CameraShake, ScreenFlash, ZoomPunch};
+use generator::{despawn_platforms_below, generate_platforms, reset_generator, PlatformGenerator};
+use hazard::{detect_hazard_hits, move_projectiles, reset_projectile_spawner, spawn_projectiles};
+use hazard::{PlayerHurt, ProjectileSpawner};
+use health::{blink_invulnerable, damage_player, lose_life, reset_safe_platform, track_safe_platform};
+use health::{spawn_health_hud, update_health_hud, Health, LifeLost, SafePlatform};
+use launcher::{launch_player, Launcher};
+use level::{Level, LevelLoader};
 use map::spawn_map;
+use map::spawn_level_platforms;
 use map::modify_body_translation;
+use map::{track_platform_displacement, Platform, PlatformDisplacement, PlatformMovement};
+use motion::move_platforms_along_paths;
+use parallax::{scroll_parallax, spawn_parallax_background};
+use powerup::{collect_power_ups, double_jump, fly_jetpack, spawn_effect_hud, tick_effect};
+use powerup::{update_effect_hud, DoubleJump, Jetpack, PlatformFreeze, Shield};
+use score::{spawn_hud, update_hud, update_score, Score};
+use shop::{equip_new_player, save_wallet, save_wallet_on_exit, shop_input, spawn_shop_screen, update_shop_text, ShopScreen, Wallet};
+use state::*;
 
 // Constants
 pub const PLAYER_SIZE: f32 = 64.0; // player size
 pub const PLAYER_SPEED: f32 = 500.0;
 pub const TILE_SIZE: f32 = 0.1;
 pub const FALL_SPEED: f32 = 1080.0;
-pub const JUMP_FORCE: f32 = 500.0;
-const ANIMATION_FRAME_TIME: f32 = 0.1;
-const BACKGROUND_COLOR: Color = Color::rgb(0.5, 2.5, 5.0);
+pub const JUMP_FORCE: f32 = 1100.0; // upward velocity at the start of a jump
+pub const RISE_GRAVITY_SCALE: f32 = 1.0; // gravity while rising with Space held
+pub const JUMP_CUT_GRAVITY_SCALE: f32 = 3.0; // gravity while rising after Space is let go, for short hops
+pub const FALL_GRAVITY_SCALE: f32 = 1.6; // gravity while falling
+pub const MAX_FALL_SPEED: f32 = 1400.0;
+pub const COYOTE_TIME: f32 = 0.1; // seconds after leaving a platform that a jump is still allowed
+pub const JUMP_BUFFER_TIME: f32 = 0.15; // seconds a Space press is remembered before landing
+pub const GROUND_NORMAL_MIN_Y: f32 = 0.7; // how upright a contact must be to count as standing on it
+pub const BACKGROUND_COLOR: Color = Color::rgb(0.5, 2.5, 5.0);
 
 fn main() {
     App::new()
@@ -27,7 +80,7 @@ fn main() {
             }),
             ..default()
         }))
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugin(RapierPhysicsPlugin::<OneWayPlatformHooks>::default())
         .insert_resource(RapierConfiguration {
             gravity: Vec2::Y * -FALL_SPEED,
             timestep_mode: TimestepMode::Fixed {
@@ -37,18 +90,124 @@ fn main() {
             ..default()
         })
         .insert_resource(ClearColor(BACKGROUND_COLOR))
+        .add_asset::<Level>()
+        .init_asset_loader::<LevelLoader>()
+        .insert_resource(PlatformGenerator::from_env())
+        .init_resource::<Score>()
+        .init_resource::<GameMode>()
+        .init_resource::<HighestPlatform>()
+        .init_resource::<ProjectileSpawner>()
+        .init_resource::<SafePlatform>()
+        .insert_resource(Wallet::load())
+        .init_resource::<Volume>()
+        .init_resource::<Music>()
+        .insert_resource(CloudCover::from_env())
+        .add_event::<PlayerHurt>()
+        .add_event::<LifeLost>()
+        .add_event::<SoundEffect>()
+        .add_event::<SnapCamera>()
+        .add_event::<CameraShake>()
+        .add_event::<ZoomPunch>()
+        .add_event::<ScreenFlash>()
+        .add_state::<GameState>()
         .add_startup_system(setup)
-        .add_startup_system(spawn_player)
-        .add_startup_system(spawn_map)
-        .add_system(modify_body_translation)
-        .add_system(camera_follow.after(move_player))
-        .add_system(move_player)
-        .add_system(confine_player_movement)
-        .add_system(apply_jump_force)
+        .add_startup_system(load_player_atlas)
+        .add_startup_system(spawn_parallax_background)
+        .add_startup_system(spawn_hud)
+        .add_startup_system(spawn_health_hud)
+        .add_startup_system(spawn_effect_hud)
+        .add_startup_system(spawn_flash_overlay)
+        .add_systems(
+            (
+                spawn_player,
+                spawn_map,
+                reset_generator,
+                reset_score,
+                reset_highest_platform,
+                reset_projectile_spawner,
+                reset_safe_platform,
+            )
+                .distributive_run_if(no_run_in_progress)
+                .in_schedule(OnEnter(GameState::Playing)),
+        )
+        .add_system(resume_physics.in_schedule(OnEnter(GameState::Playing)))
+        .add_system(pause_physics.in_schedule(OnExit(GameState::Playing)))
+        .add_system(spawn_main_menu.in_schedule(OnEnter(GameState::MainMenu)))
+        .add_system(despawn_run.in_schedule(OnEnter(GameState::MainMenu)))
+        .add_system(despawn_with::<MainMenuScreen>.in_schedule(OnExit(GameState::MainMenu)))
+        .add_system(spawn_pause_screen.in_schedule(OnEnter(GameState::Paused)))
+        .add_system(despawn_with::<PauseScreen>.in_schedule(OnExit(GameState::Paused)))
+        .add_system(spawn_game_over_screen.in_schedule(OnEnter(GameState::GameOver)))
+        .add_system(despawn_with::<GameOverScreen>.in_schedule(OnExit(GameState::GameOver)))
+        .add_system(spawn_victory_screen.in_schedule(OnEnter(GameState::Victory)))
+        .add_system(despawn_with::<VictoryScreen>.in_schedule(OnExit(GameState::Victory)))
+        .add_system(spawn_shop_screen.in_schedule(OnEnter(GameState::Shop)))
+        .add_system(despawn_with::<ShopScreen>.in_schedule(OnExit(GameState::Shop)))
+        .add_system(save_wallet.in_schedule(OnExit(GameState::Playing)))
+        .add_system(save_wallet_on_exit.in_base_set(CoreSet::Last))
+        .add_system(main_menu_input.in_set(OnUpdate(GameState::MainMenu)))
+        .add_system(update_mode_text.in_set(OnUpdate(GameState::MainMenu)))
+        .add_system(playing_input.in_set(OnUpdate(GameState::Playing)))
+        .add_system(paused_input.in_set(OnUpdate(GameState::Paused)))
+        .add_system(game_over_input.in_set(OnUpdate(GameState::GameOver)))
+        .add_system(victory_input.in_set(OnUpdate(GameState::Victory)))
+        .add_system(shop_input.in_set(OnUpdate(GameState::Shop)))
+        .add_system(update_shop_text.in_set(OnUpdate(GameState::Shop)))
+        .add_system(spawn_level_platforms.in_set(OnUpdate(GameState::Playing)))
+        .add_system(generate_platforms.after(spawn_level_platforms).in_set(OnUpdate(GameState::Playing)))
+        .add_system(despawn_platforms_below.in_set(OnUpdate(GameState::Playing)))
+        .add_system(modify_body_translation.in_set(PlatformMovement).in_set(OnUpdate(GameState::Playing)))
+        .add_system(move_platforms_along_paths.in_set(PlatformMovement).in_set(OnUpdate(GameState::Playing)))
+        .add_system(launch_player.after(update_grounded).after(move_player).in_set(OnUpdate(GameState::Playing)))
+        .add_system(update_special_platforms.after(update_grounded).in_set(OnUpdate(GameState::Playing)))
+        .add_system(track_platform_displacement.after(PlatformMovement).in_set(OnUpdate(GameState::Playing)))
+        .add_system(carry_player.after(track_platform_displacement).after(update_grounded).in_set(OnUpdate(GameState::Playing)))
+        .add_system(camera_follow.after(move_player).in_set(OnUpdate(GameState::Playing)))
+        .add_system(apply_camera_feel.after(camera_follow))
+        .add_system(fade_screen_flash)
+        .add_system(scroll_parallax.after(apply_camera_feel))
+        .add_system(apply_biome.after(camera_follow))
+        .add_system(maintain_cloud_count.after(apply_biome))
+        .add_system(drift_clouds.after(camera_follow))
+        .add_system(update_grounded.before(move_player).in_set(OnUpdate(GameState::Playing)))
+        .add_system(move_player.in_set(OnUpdate(GameState::Playing)))
+        .add_system(confine_player_movement.in_set(OnUpdate(GameState::Playing)))
+        .add_system(apply_jump_force.in_set(OnUpdate(GameState::Playing)))
+        .add_system(update_score.after(confine_player_movement).in_set(OnUpdate(GameState::Playing)))
+        .add_system(check_victory.after(update_score).after(select_player_state).in_set(OnUpdate(GameState::Playing)))
+        .add_system(track_highest_platform.after(update_grounded).in_set(OnUpdate(GameState::Playing)))
+        .add_system(spawn_projectiles.after(update_score).in_set(OnUpdate(GameState::Playing)))
+        .add_system(move_projectiles.in_set(OnUpdate(GameState::Playing)))
+        .add_system(detect_hazard_hits.in_set(OnUpdate(GameState::Playing)))
+        .add_system(damage_player.after(detect_hazard_hits).after(select_player_state).in_set(OnUpdate(GameState::Playing)))
+        .add_system(check_fall_death.after(track_highest_platform).after(camera_follow).in_set(OnUpdate(GameState::Playing)))
+        .add_system(track_safe_platform.after(update_grounded).in_set(OnUpdate(GameState::Playing)))
+        .add_system(lose_life.after(damage_player).after(check_fall_death).after(track_safe_platform).in_set(OnUpdate(GameState::Playing)))
+        .add_system(blink_invulnerable.in_set(OnUpdate(GameState::Playing)))
+        .add_system(collect_power_ups.in_set(OnUpdate(GameState::Playing)))
+        .add_system(tick_effect::<Jetpack>.in_set(OnUpdate(GameState::Playing)))
+        .add_system(tick_effect::<Shield>.in_set(OnUpdate(GameState::Playing)))
+        .add_system(tick_effect::<DoubleJump>.in_set(OnUpdate(GameState::Playing)))
+        .add_system(tick_effect::<PlatformFreeze>.in_set(OnUpdate(GameState::Playing)))
+        .add_system(fly_jetpack.after(apply_jump_force).in_set(OnUpdate(GameState::Playing)))
+        .add_system(double_jump.after(move_player).in_set(OnUpdate(GameState::Playing)))
+        .add_system(equip_new_player.in_set(OnUpdate(GameState::Playing)))
+        .add_system(collect_coins.in_set(OnUpdate(GameState::Playing)))
+        .add_system(animate_coin_pickups.in_set(OnUpdate(GameState::Playing)))
+        .add_system(select_player_state.after(move_player).after(apply_jump_force).after(launch_player).after(double_jump).in_set(OnUpdate(GameState::Playing)))
+        .add_system(animate_player.after(select_player_state).after(damage_player).after(lose_life).after(check_victory).in_set(OnUpdate(GameState::Playing)))
+        .add_system(update_hud.after(update_score))
+        .add_system(update_health_hud)
+        .add_system(update_effect_hud::<Jetpack>)
+        .add_system(update_effect_hud::<Shield>)
+        .add_system(update_effect_hud::<DoubleJump>)
+        .add_system(update_effect_hud::<PlatformFreeze>)
+        .add_system(play_music)
+        .add_system(volume_input)
+        .add_system(apply_music_volume.after(play_music).after(volume_input))
+        .add_system(play_sound_effects)
         // Uncomment below to see physics colliders (for debug use)
         //.add_plugin(RapierDebugRenderPlugin::default())
-        // Uncomment below to recreate the bug with music
-        //.add_system(music_setup)
         .run();
 }
 
@@ -56,23 +215,29 @@ pub fn hello_world() {
     println!("hello world")
 }
 
-#[derive(Component)]
-pub struct AnimationTimer(Timer);
-
 // Update Player struct
 #[derive(Component, Default)]
 pub struct Player {
     state: PlayerState,
-    animation_frame: bool, // alternates between true/false for walk1/walk2
+    grounded_on: Option<Entity>, // platform the player is standing on, if any
+    time_since_grounded: f32,
+    jump_buffer: f32, // time left on the last Space press
+    knockback: f32, // seconds left before the player can steer again after being hit
 }
 
-#[derive(Component, Default)]
+// WHAT THE PLAYER IS DOING, EACH STATE HAS ITS OWN ANIMATION CLIP
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq)]
 pub enum PlayerState {
     #[default]
     Idle,
     Left,
     Right,
     Jump,
+    Fall,
+    Hurt,
+    Cheer,
+    Skid,
+    Duck,
 }
 
 
@@ -80,7 +245,7 @@ pub enum PlayerState {
 pub fn spawn_player(
     mut commands: Commands,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    player_atlas: Res<PlayerAtlas>,
 ) {
     let window: &Window = window_query.get_single().unwrap();
 
@@ -88,141 +253,189 @@ pub fn spawn_player(
         .spawn(RigidBody::Dynamic)
         .insert(LockedAxes::ROTATION_LOCKED)
         .insert(Collider::cuboid(20.0, 49.0))
-        .insert(Damping {
-            linear_damping: 5.0,
-            angular_damping: 10.0,
-        })
-        .insert(Restitution::coefficient(1.0))
+        .insert(Velocity::zero())
+        .insert(GravityScale(FALL_GRAVITY_SCALE))
+        .insert(Ccd::enabled())
+        .insert(Restitution::coefficient(0.0))
         .insert(Friction::coefficient(0.0))
-        .insert(KinematicCharacterController::default())
-        .insert(SpriteBundle {
+        .insert(SpriteSheetBundle {
             transform: Transform::from_xyz(window.width() / 2.0, window.height() / 1.0 - 50.0, 0.0),
-            texture: asset_server.load("sprites/Adventurer/Poses/adventurer_cheer1.png"),
+            texture_atlas: player_atlas.0.clone(),
             ..default()
         })
-        .insert(Player::default())
-        .insert(AnimationTimer(Timer::from_seconds(ANIMATION_FRAME_TIME, TimerMode::Repeating)));
-}
-
-#[derive(Component)]
-pub struct BackGround {
-    pos: Vec3,
+        .insert(Player {
+            state: PlayerState::Cheer,
+            ..default()
+        })
+        .insert(Health::default())
+        .insert(PlayerAnimation::default());
 }
 
-// ADD TEXTURE OF BACKGROUND TO THE GAME WITH CLOUDS COVERING THE PLATFORM AND PLAYER FOR ADDED DIFFICULTY
+// ADD THE CAMERA, THE BACKGROUND AND CLOUDS ARE SPAWNED BY THE PARALLAX AND CLOUD MODULES
 pub fn setup(
     mut commands: Commands,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
 ) {
     let window: &Window = window_query.get_single().unwrap();
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(window.width() / 2.0, window.height(), 0.0),
-        ..default()
-    });
-
-    commands
-        .spawn(SpriteBundle {
-            transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, -5.0),
-            texture: asset_server.load("backgrounds/BG_DesertMountains/background2.png"),
+    let position = Vec2::new(window.width() / 2.0, window.height());
+    commands.spawn((
+        Camera2dBundle {
+            transform: Transform::from_xyz(position.x, position.y, 0.0),
             ..default()
-        })
-        .insert(BackGround { pos: Vec3::ZERO });
+        },
+        CameraController::from_env(position),
+        CameraFeel::default(),
+    ));
+}
 
-    // Spawn clouds in a loop
-    for i in (0..12000).step_by(500) {
-        commands.spawn(SpriteBundle {
-            transform: Transform::from_xyz(window.width() / 2.0 + if i % 1000 == 0 { 20.0 } else { -20.0 }, window.height() / 2.0 + i as f32, 0.0),
-            texture: asset_server.load("clouds.png"),
-            ..default()
-        });
-    }
+// PRESENT WHILE THE PLAYER IS RISING FROM A JUMP, held IS CLEARED ONCE SPACE IS LET GO
+#[derive(Component)]
+pub struct Jump {
+    pub held: bool,
+    pub launched: bool, // thrown by a spring or trampoline, so letting go of Space doesn't cut it short
 }
 
-// FOLLOWS THE PLAYER CHARACTER WITH A TRANSLATION OF 150 PIXELS SO PLAYER IS ON BOTTOM OF SCREEN AND YOU CAN SEE PLATFORMS ABOVE
-fn camera_follow(
-    player_query: Query<&Transform, With<Player>>,
-    mut camera_query: Query<&mut Transform, (Without<Player>, With<Camera2d>)>,
+// ANYTHING THE PLAYER CAN STAND ON
+type Ground = Or<(With<Platform>, With<Launcher>)>;
+
+// CHECKS THE PLAYER'S CONTACTS FOR A PLATFORM UNDERNEATH THEM
+pub fn update_grounded(
+    rapier_context: Res<RapierContext>,
+    mut player_query: Query<(Entity, &mut Player)>,
+    platform_query: Query<(), Ground>,
+    time: Res<Time>,
 ) {
-    let player_transform = player_query.single();
-    let mut camera_transform = camera_query.single_mut();
+    let Ok((player_entity, mut player)) = player_query.get_single_mut() else {
+        return;
+    };
 
-    camera_transform.translation.x = player_transform.translation.x;
-    camera_transform.translation.y = player_transform.translation.y + 150.0;
-}
+    let mut grounded_on = None;
+    for contact_pair in rapier_context.contacts_with(player_entity) {
+        if !contact_pair.has_any_active_contacts() {
+            continue;
+        }
+        let (other, flip) = if contact_pair.collider1() == player_entity {
+            (contact_pair.collider2(), -1.0)
+        } else {
+            (contact_pair.collider1(), 1.0)
+        };
+        if !platform_query.contains(other) {
+            continue;
+        }
+        // manifold normals point from collider1 to collider2, flip them so they point at the player
+        let standing = contact_pair
+            .manifolds()
+            .any(|manifold| manifold.normal().y * flip >= GROUND_NORMAL_MIN_Y);
+        if standing {
+            grounded_on = Some(other);
+            break;
+        }
+    }
 
+    player.grounded_on = grounded_on;
+    if grounded_on.is_some() {
+        player.time_since_grounded = 0.0;
+    } else {
+        player.time_since_grounded += time.delta_seconds();
+    }
+}
 
-#[derive(Component)]
-pub struct Jump(pub f32);
+// MOVES THE PLAYER ALONG WITH WHATEVER PLATFORM THEY ARE STANDING ON
+pub fn carry_player(
+    mut player_query: Query<(&Player, &mut Transform)>,
+    platform_query: Query<&PlatformDisplacement>,
+) {
+    let Ok((player, mut player_transform)) = player_query.get_single_mut() else {
+        return;
+    };
+    let Some(displacement) = player.grounded_on.and_then(|platform| platform_query.get(platform).ok()) else {
+        return;
+    };
+    player_transform.translation.x += displacement.delta.x;
+    player_transform.translation.y += displacement.delta.y;
+}
 
+#[allow(clippy::too_many_arguments)]
 pub fn move_player(
-    mut controllers: Query<&mut KinematicCharacterController>,
     keyboard: Res<Input<KeyCode>>,
     time: Res<Time>,
-    mut player_query: Query<(Entity, &mut Player, &mut Handle<Image>, &mut AnimationTimer), With<Player>>,
-    asset_server: Res<AssetServer>,
+    mut player_query: Query<(Entity, &mut Player, &mut Velocity)>,
+    jumping_query: Query<(), With<Jump>>,
+    launcher_query: Query<(), With<Launcher>>,
+    game_mode: Res<GameMode>,
+    mut sound_events: EventWriter<SoundEffect>,
     mut commands: Commands,
 ) {
-    let mut to_move: Vec2 = Vec2::ZERO;
-    
-    if let Ok((entity, mut player, mut sprite, mut timer)) = player_query.get_single_mut() {
-        timer.0.tick(time.delta());
+    if let Ok((entity, mut player, mut velocity)) = player_query.get_single_mut() {
+        let mut to_move: f32 = 0.0;
 
-        // Handle movement and sprite changes
+        // Handle movement, the pose is picked from the resulting velocity by select_player_state
         if keyboard.pressed(KeyCode::Left) {
-            to_move.x -= PLAYER_SPEED;
-            if timer.0.just_finished() {
-                player.animation_frame = !player.animation_frame;
-                *sprite = asset_server.load(if player.animation_frame {
-                    "sprites/Adventurer/Poses/adventurer_walk1.png"
-                } else {
-                    "sprites/Adventurer/Poses/adventurer_walk2.png"
-                });
-            }
-            player.state = PlayerState::Left;
+            to_move -= PLAYER_SPEED;
         } else if keyboard.pressed(KeyCode::Right) {
-            to_move.x += PLAYER_SPEED;
-            if timer.0.just_finished() {
-                player.animation_frame = !player.animation_frame;
-                *sprite = asset_server.load(if player.animation_frame {
-                    "sprites/Adventurer/Poses/adventurer_walk1.png"
-                } else {
-                    "sprites/Adventurer/Poses/adventurer_walk2.png"
-                });
-            }
-            player.state = PlayerState::Right;
+            to_move += PLAYER_SPEED;
+        }
+        // a knockback carries the player on its own until it wears off
+        if player.knockback > 0.0 {
+            player.knockback -= time.delta_seconds();
         } else {
-            *sprite = asset_server.load("sprites/Adventurer/Poses/adventurer_idle.png");
-            player.state = PlayerState::Idle;
+            velocity.linvel.x = to_move;
         }
-        // Handle jumping
+
+        // Handle jumping, a press is buffered briefly and only used from the ground or just after leaving it
         if keyboard.just_pressed(KeyCode::Space) {
-            commands.entity(entity).insert(Jump(JUMP_FORCE));
-            *sprite = asset_server.load("sprites/Adventurer/Poses/adventurer_climb1.png");
-            player.state = PlayerState::Jump;
+            player.jump_buffer = JUMP_BUFFER_TIME;
+        } else {
+            player.jump_buffer = (player.jump_buffer - time.delta_seconds()).max(0.0);
+        }
+        let can_jump = player.grounded_on.is_some() || player.time_since_grounded <= COYOTE_TIME;
+        // Classic mode bounces on its own as soon as the player lands
+        let wants_jump = player.jump_buffer > 0.0
+            || (*game_mode == GameMode::Classic && player.grounded_on.is_some());
+        // springs and trampolines throw the player themselves in launch_player
+        let on_launcher = player.grounded_on.is_some_and(|ground| launcher_query.contains(ground));
+        if wants_jump && can_jump && !on_launcher && jumping_query.is_empty() {
+            player.jump_buffer = 0.0;
+            player.time_since_grounded = COYOTE_TIME + 1.0;
+            velocity.linvel.y = JUMP_FORCE;
+            commands.entity(entity).insert(Jump {
+                held: true,
+                launched: false,
+            });
+            sound_events.send(SoundEffect::Jump);
         }
-    }
-
-    for mut controller in controllers.iter_mut() {
-        controller.translation = Some(to_move * time.delta_seconds());
     }
 }
 
-// Update apply_jump_force system
+// PICKS THE GRAVITY FOR THE CURRENT PART OF THE JUMP AND ENDS THE JUMP AT ITS PEAK
 pub fn apply_jump_force(
     mut commands: Commands,
-    mut player_query: Query<(Entity, &mut Transform, &mut Jump, &mut Handle<Image>), With<Player>>,
-    time: Res<Time>,
-    asset_server: Res<AssetServer>,
+    mut player_query: Query<(Entity, &mut Velocity, &mut GravityScale), With<Player>>,
+    mut jump_query: Query<&mut Jump>,
+    keyboard: Res<Input<KeyCode>>,
+    game_mode: Res<GameMode>,
 ) {
-    if let Ok((entity, mut transform, mut jump, mut sprite)) = player_query.get_single_mut() {
-        let jump_power = (time.delta_seconds() * FALL_SPEED * 2.0).min(jump.0);
-        jump.0 -= jump_power;
-        transform.translation.y += jump_power;
-        
-        if jump.0 <= 0.0 {
+    if let Ok((entity, mut velocity, mut gravity_scale)) = player_query.get_single_mut() {
+        velocity.linvel.y = velocity.linvel.y.max(-MAX_FALL_SPEED);
+
+        let Ok(mut jump) = jump_query.get_mut(entity) else {
+            gravity_scale.0 = FALL_GRAVITY_SCALE;
+            return;
+        };
+
+        // letting go of Space early cuts the jump short, classic mode always bounces at full height
+        if *game_mode == GameMode::Standard && !jump.launched && !keyboard.pressed(KeyCode::Space) {
+            jump.held = false;
+        }
+        gravity_scale.0 = if jump.held {
+            RISE_GRAVITY_SCALE
+        } else {
+            JUMP_CUT_GRAVITY_SCALE
+        };
+
+        if velocity.linvel.y <= 0.0 {
             commands.entity(entity).remove::<Jump>();
-            *sprite = asset_server.load("sprites/Adventurer/Poses/adventurer_idle.png");
+            gravity_scale.0 = FALL_GRAVITY_SCALE;
         }
     }
 }
@@ -237,8 +450,6 @@ pub fn confine_player_movement(
         let half_player_size: f32 = PLAYER_SIZE / 2.0 - 50.0; // 32.0
         let x_min = 25.0 + half_player_size;
         let x_max = window.width() - half_player_size;
-        let y_min = 50.0 + half_player_size;
-        let y_max = window.height() + 100000.0;
 
         let mut translation: Vec3 = player_transform.translation;
 
@@ -248,17 +459,6 @@ pub fn confine_player_movement(
             translation.x = x_max;
         }
 
-        if translation.y < y_min {
-            translation.y = y_min;
-        } else if translation.y > y_max {
-            translation.y = y_max;
-        }
-
         player_transform.translation = translation;
     }
 }
-
-pub fn music_setup(asset_server: Res<AssetServer>, audio: Res<Audio>) {
-    let music = asset_server.load("Child's Nightmare.ogg");
-    audio.play(music);
-}
\ No newline at end of file
@@ -2,28 +2,85 @@ use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_rapier2d::prelude::*;
 use bevy::window::PrimaryWindow;
+use serde::Deserialize;
 
-#[derive(Component)]
+use crate::behavior::SpecialPlatform;
+use crate::biome::BiomeTinted;
+use crate::coin::spawn_coin;
+use crate::hazard::{spawn_enemy, spawn_spikes};
+use crate::launcher::{spawn_spring, TRAMPOLINE_COLOR};
+use crate::level::{Level, PlatformDesc};
+use crate::motion::PathMover;
+use crate::powerup::{platform_time_scale, spawn_power_up, PlatformFreeze};
+
+pub const LEVEL_PATH: &str = "levels/desert.level.ron";
+
+#[derive(Component, Deserialize, Clone)]
 pub struct MovingPlatform {
-    direction: i32,
-    max_x: f32,
-    min_x: f32,
+    pub direction: i32,
+    pub max_x: f32,
+    pub min_x: f32,
+    // PIXELS PER SECOND AWAY FROM THE ENDS OF THE RANGE
+    #[serde(default = "default_platform_speed")]
+    pub speed: f32,
+    // DISTANCE FROM EACH END OVER WHICH THE PLATFORM SLOWS DOWN AND SPEEDS BACK UP, 0 TURNS INSTANTLY
+    #[serde(default = "default_ease_distance")]
+    pub ease_distance: f32,
 }
 
+pub const DEFAULT_PLATFORM_SPEED: f32 = 60.0;
+pub const DEFAULT_EASE_DISTANCE: f32 = 40.0;
+// SLOWEST A PLATFORM GETS WHILE TURNING AROUND, AS A FRACTION OF ITS SPEED
+const MIN_EASE_FACTOR: f32 = 0.2;
+
+fn default_platform_speed() -> f32 {
+    DEFAULT_PLATFORM_SPEED
+}
+
+fn default_ease_distance() -> f32 {
+    DEFAULT_EASE_DISTANCE
+}
+
+impl MovingPlatform {
+    // FRACTION OF FULL SPEED AT x, EASING IN AND OUT OF THE ENDS OF THE RANGE WITH A SMOOTHSTEP
+    fn speed_factor(&self, x: f32) -> f32 {
+        let distance_to_end = (x - self.min_x).min(self.max_x - x);
+        if self.ease_distance <= 0.0 || distance_to_end < 0.0 {
+            return 1.0;
+        }
+        let t = (distance_to_end / self.ease_distance).min(1.0);
+        MIN_EASE_FACTOR + (1.0 - MIN_EASE_FACTOR) * t * t * (3.0 - 2.0 * t)
+    }
+}
+
+// MARKS EVERY PLATFORM SO THEY CAN BE FOUND AND CLEANED UP REGARDLESS OF WHO SPAWNED THEM
+#[derive(Component)]
+pub struct Platform;
+
+// HOW FAR A PLATFORM MOVED THIS FRAME, WHATEVER MOVED IT, SO IT CAN CARRY THE PLAYER ALONG
+#[derive(Component, Default)]
+pub struct PlatformDisplacement {
+    last_position: Option<Vec3>,
+    pub delta: Vec3,
+}
+
+// EVERY SYSTEM THAT MOVES PLATFORMS GOES IN THIS SET SO DISPLACEMENT IS MEASURED AFTER THEM
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlatformMovement;
+
 // MOVES PLATFORMS SIDE TO SIDE WITH RANGE OF X DIRECTION PARAMETERS
 pub fn modify_body_translation(
     mut query: Query<(&mut Transform, &mut MovingPlatform)>,
-    mut direction: Local<i32>,
+    freeze_query: Query<(), With<PlatformFreeze>>,
+    time: Res<Time>,
 ) {
-    if *direction != 1 && *direction != -1 {
-        *direction = 1;
-    }
-
+    let dt = time.delta_seconds() * platform_time_scale(&freeze_query);
     for (mut position, mut moving_platform) in query.iter_mut() {
+        let step = moving_platform.speed * moving_platform.speed_factor(position.translation.x) * dt;
         if moving_platform.direction == 1 {
-            position.translation.x += 1.0;
+            position.translation.x += step;
         } else {
-            position.translation.x -= 1.0;
+            position.translation.x -= step;
         }
 
         if position.translation.x >= moving_platform.max_x {
@@ -33,740 +90,123 @@ pub fn modify_body_translation(
         }
     }
 }
-// CREATES AND SPAWNS PLATFORMS MANUALLY...WITH ITERATIONS IN SIZE OF PLATFORMS, RANGE OF MOVEMENT, AND COLOR
-pub fn spawn_map(
+
+// RECORDS HOW FAR EACH PLATFORM MOVED SINCE LAST FRAME
+pub fn track_platform_displacement(mut query: Query<(&Transform, &mut PlatformDisplacement)>) {
+    for (transform, mut displacement) in query.iter_mut() {
+        displacement.delta = displacement
+            .last_position
+            .map_or(Vec3::ZERO, |last_position| transform.translation - last_position);
+        displacement.last_position = Some(transform.translation);
+    }
+}
+
+// HOLDS THE LEVEL BEING PLAYED AND WHETHER ITS PLATFORMS HAVE BEEN SPAWNED YET
+#[derive(Resource)]
+pub struct CurrentLevel {
+    pub handle: Handle<Level>,
+    pub spawned: bool,
+    // WORLD Y OF THE HIGHEST PLATFORM IN THE LEVEL FILE
+    pub top: f32,
+    // SET ONCE THE PLAYER HAS CLIMBED PAST top
+    pub completed: bool,
+}
+
+// STARTS LOADING THE LEVEL FILE, THE PLATFORMS ARE SPAWNED BY spawn_level_platforms ONCE IT IS READY
+pub fn spawn_map(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CurrentLevel {
+        handle: asset_server.load(LEVEL_PATH),
+        spawned: false,
+        top: 0.0,
+        completed: false,
+    });
+}
+
+// SPAWNS EVERY PLATFORM OF THE CURRENT LEVEL ONCE THE ASSET HAS FINISHED LOADING
+pub fn spawn_level_platforms(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    _asset_server: Res<AssetServer>,
+    levels: Res<Assets<Level>>,
+    mut current_level: ResMut<CurrentLevel>,
 ) {
-    let width = 300.0;
-    let height = 30.0;
-
+    if current_level.spawned {
+        return;
+    }
+    let Some(level) = levels.get(&current_level.handle) else {
+        return;
+    };
     let window: &Window = window_query.get_single().unwrap();
+    let origin = Vec2::new(window.width() / 2.0, window.height() / 2.0);
 
-    commands
-        .spawn(RigidBody::KinematicPositionBased)
-        .insert(Collider::cuboid(width/2.0, height/2.0)) 
-        .insert(MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Quad::new(Vec2::new(width, height)).into()).into(),
-            material: materials.add(ColorMaterial::from(Color::BLACK)),
-            ..default()
-        })
-        .insert(TransformBundle::from(Transform::from_xyz(
-            window.width() / 2.0,
-            window.height() / 2.0,
-            0.0,
-    )));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(10.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 20.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0,
-                window.height() / 2.0 + 12000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 100.0,
-            max_x: 150.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(10.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 20.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0,
-                window.height() / 2.0 + 11500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 200.0,
-            max_x: 250.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(10.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 20.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0,
-                window.height() / 2.0 + 11000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 250.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(10.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 20.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0,
-                window.height() / 2.0 + 10500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 300.0,
-            max_x: 350.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(10.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 20.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0,
-                window.height() / 2.0 + 10000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 200.0,
-            max_x: 250.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(20.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 40.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 9500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 200.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(20.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 40.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 9000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(20.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 40.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 8500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(20.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 40.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 8000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(30.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 60.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 7500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(30.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 60.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 7000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(40.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 80.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 6500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(40.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 80.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 6000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-    
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(40.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 80.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 5500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 5000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 4500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 4000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 3500.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 3000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 2750.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 200.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 2350.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 50.0,
-            max_x: 300.0,
-        }
-    ));
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 200.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 2000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 5.0,
-            max_x: 550.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(150.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 300.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 1750.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 5.0,
-            max_x: 550.0,
-        }
-    ));
-
-
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(50.0, 10.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 100.0,
-                y: 20.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 30.0,
-                window.height() / 2.0 + 1450.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 5.0,
-            max_x: 550.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 10.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x:200.0,
-                y: 20.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 50.0,
-                window.height() / 2.0 + 1150.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 5.0,
-            max_x: 550.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(90.0, 5.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x: 180.0,
-                y: 10.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 150.0,
-                window.height() / 2.0 + 880.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 5.0,
-            max_x: 100.0,
-        }
-    ));
-
-    commands
-    .spawn(RigidBody::KinematicPositionBased)
-    .insert(Collider::cuboid(width/2.0, height/2.0)) 
-    .insert(MaterialMesh2dBundle {
-        mesh: meshes.add(shape::Quad::new(Vec2::new(width, height)).into()).into(),
-        material: materials.add(ColorMaterial::from(Color::MIDNIGHT_BLUE)),
-        ..default()
-    })
-    .insert(TransformBundle::from(Transform::from_xyz(
-        window.width() / 2.0 - 100.0,
-        window.height() / 2.0 + 800.0,
-        0.0,
-    )))
-    .insert(MovingPlatform {
-        direction: 1,
-        min_x: 50.0,
-        max_x: 300.0
-    });
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 10.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x:200.0,
-                y: 20.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 75.0,
-                window.height() / 2.0 + 650.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 30.0,
-            max_x: 500.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 10.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x:200.0,
-                y: 20.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::ORANGE_RED)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 - 100.0,
-                window.height() / 2.0 + 450.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: -1,
-            min_x: 0.0,
-            max_x: 450.0,
-        }
-    ));
-
-    commands.spawn((
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(100.0, 15.0),
-        MaterialMesh2dBundle {
-            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2 {
-                x:200.0,
-                y: 30.0,
-            }))).into(),
-            material: materials.add(ColorMaterial::from(Color::BEIGE)),
-            transform: Transform::from_translation(Vec3::new(
-                window.width() / 2.0 + 200.0,
-                window.height() / 2.0 + 1000.0,
-                0.0)),
-            ..default()
-        },
-        MovingPlatform {
-            direction: 1,
-            min_x: 100.0,
-            max_x: 300.0,
-        }
-    ));
-
-    commands
-    .spawn(RigidBody::KinematicPositionBased)
-    .insert(Collider::cuboid(width/2.0, height/2.0)) 
-    .insert(MovingPlatform {
-        direction: 1,
-        min_x: 0.0,
-        max_x: 200.0
-    })
-    .insert(MaterialMesh2dBundle {
-        mesh: meshes.add(shape::Quad::new(Vec2::new(width, height)).into()).into(),
-        material: materials.add(ColorMaterial::from(Color::ALICE_BLUE)),
-        ..default()
-    })
-    .insert(TransformBundle::from(Transform::from_xyz(
-        window.width() / 2.0 - 200.0,
-        window.height() / 2.0 + 300.0,
-        0.0,
-    )));
+    let mut top = origin.y;
+    for platform in &level.platforms {
+        spawn_platform(&mut commands, &mut meshes, &mut materials, origin, platform);
+        top = top.max(origin.y + platform.offset.y);
+    }
+    for enemy in &level.enemies {
+        spawn_enemy(&mut commands, &mut meshes, &mut materials, origin, enemy);
+    }
+    for power_up in &level.power_ups {
+        spawn_power_up(&mut commands, &mut meshes, &mut materials, origin, power_up);
+    }
+    for offset in &level.coins {
+        spawn_coin(&mut commands, &mut meshes, &mut materials, origin, *offset);
+    }
+    current_level.top = top;
+    current_level.spawned = true;
+}
 
-    //ball
-    /* 
-    commands
-        .spawn(RigidBody::Dynamic)
-        .insert(Collider::ball(50.0))
-        .insert(MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::new(50.0).into()).into(),
-            material: materials.add(ColorMaterial::from(Color::PURPLE)),
-            ..default()
-        })
-        .insert(Restitution::coefficient(0.7))
-        .insert(TransformBundle::from(Transform::from_xyz(
-            (window.width() / 2.0) - 50.0,
-            window.height() / 1.0,
-            0.0,
-        )));
-        */
+// SPAWNS A SINGLE PLATFORM WITH ITS COLLIDER, MESH AND OPTIONAL SIDE TO SIDE MOVEMENT
+pub fn spawn_platform(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    origin: Vec2,
+    platform: &PlatformDesc,
+) -> Entity {
+    let position = origin + platform.offset;
+    let color = match (&platform.behavior, platform.trampoline) {
+        (Some(behavior), _) => behavior.color(),
+        (None, Some(_)) => TRAMPOLINE_COLOR,
+        (None, None) => platform.color,
+    };
+    let mut entity = commands.spawn((
+        Platform,
+        PlatformDisplacement::default(),
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(platform.size.x / 2.0, platform.size.y / 2.0),
+        ActiveHooks::MODIFY_SOLVER_CONTACTS,
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Mesh::from(shape::Quad::new(platform.size))).into(),
+            material: materials.add(ColorMaterial::from(color)),
+            transform: Transform::from_xyz(position.x, position.y, 0.0),
+            ..default()
+        },
+    ));
+    if let Some(moving) = &platform.moving {
+        entity.insert(moving.clone());
+    }
+    if let Some(path) = &platform.path {
+        entity.insert(PathMover::new(path.clone(), position));
+    }
+    if let Some(behavior) = &platform.behavior {
+        entity.insert(SpecialPlatform::new(behavior.clone()));
+    }
+    if let Some(trampoline) = platform.trampoline {
+        entity.insert(trampoline);
+    }
+    // a behavior or trampoline color says what the platform does, so it isn't tinted over
+    if platform.biome_tint && platform.behavior.is_none() && platform.trampoline.is_none() {
+        entity.insert(BiomeTinted);
+    }
+    let entity = entity.id();
+    if let Some(spring) = &platform.spring {
+        spawn_spring(commands, meshes, materials, entity, platform.size.y, spring);
+    }
+    if let Some(spikes) = &platform.spikes {
+        spawn_spikes(commands, meshes, materials, entity, platform.size.y, spikes);
+    }
+    entity
 }
@@ -0,0 +1,207 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::map::DEFAULT_PLATFORM_SPEED;
+use crate::powerup::{platform_time_scale, PlatformFreeze};
+
+// A MOVEMENT PATTERN FOR A PLATFORM, ALL POSITIONS ARE RELATIVE TO WHERE THE PLATFORM WAS SPAWNED
+#[derive(Deserialize, Clone)]
+pub enum PlatformPath {
+    // BOBS UP AND DOWN BY amplitude PIXELS, ONE FULL CYCLE EVERY period SECONDS
+    Vertical { amplitude: f32, period: f32 },
+    // CIRCLES (OR ELLIPSES WHEN THE RADII DIFFER) AROUND THE SPAWN POINT, phase IS IN TURNS
+    Orbit {
+        radii: Vec2,
+        period: f32,
+        #[serde(default)]
+        phase: f32,
+    },
+    // VISITS EACH WAYPOINT IN TURN AND LOOPS BACK TO THE FIRST
+    Waypoints(Vec<Waypoint>),
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Waypoint {
+    pub offset: Vec2,
+    // PIXELS PER SECOND ON THE WAY TO THE NEXT WAYPOINT
+    #[serde(default = "default_waypoint_speed")]
+    pub speed: f32,
+    // SECONDS TO WAIT HERE BEFORE MOVING ON
+    #[serde(default)]
+    pub pause: f32,
+}
+
+fn default_waypoint_speed() -> f32 {
+    DEFAULT_PLATFORM_SPEED
+}
+
+// MOVES A PLATFORM ALONG ITS PlatformPath
+#[derive(Component)]
+pub struct PathMover {
+    pub path: PlatformPath,
+    anchor: Vec2,
+    elapsed: f32,
+    // WAYPOINT THE PLATFORM IS CURRENTLY HEADING AWAY FROM
+    segment: usize,
+    segment_progress: f32,
+    pause_left: f32,
+}
+
+impl PathMover {
+    pub fn new(path: PlatformPath, anchor: Vec2) -> Self {
+        let pause_left = match &path {
+            PlatformPath::Waypoints(points) => points.first().map_or(0.0, |point| point.pause),
+            _ => 0.0,
+        };
+        Self {
+            path,
+            anchor,
+            elapsed: 0.0,
+            segment: 0,
+            segment_progress: 0.0,
+            pause_left,
+        }
+    }
+
+    // ADVANCES THE PATH BY delta SECONDS AND RETURNS THE NEW POSITION
+    fn advance(&mut self, delta: f32) -> Vec2 {
+        self.elapsed += delta;
+        match &self.path {
+            PlatformPath::Vertical { amplitude, period } => {
+                let angle = TAU * self.elapsed / period.max(f32::EPSILON);
+                self.anchor + Vec2::new(0.0, amplitude * angle.sin())
+            }
+            PlatformPath::Orbit { radii, period, phase } => {
+                let angle = TAU * (phase + self.elapsed / period.max(f32::EPSILON));
+                self.anchor + Vec2::new(radii.x * angle.cos(), radii.y * angle.sin())
+            }
+            PlatformPath::Waypoints(points) => {
+                if points.len() < 2 {
+                    return self.anchor + points.first().map_or(Vec2::ZERO, |point| point.offset);
+                }
+
+                let mut remaining = delta;
+                // bounded so a path whose waypoints all sit on top of each other can't spin forever
+                for _ in 0..points.len() * 2 {
+                    if self.pause_left > 0.0 {
+                        let waited = self.pause_left.min(remaining);
+                        self.pause_left -= waited;
+                        remaining -= waited;
+                    }
+                    let from = &points[self.segment];
+                    let to = &points[(self.segment + 1) % points.len()];
+                    let length = from.offset.distance(to.offset);
+                    let travel = from.speed * remaining;
+
+                    if remaining <= 0.0 || self.segment_progress + travel < length {
+                        self.segment_progress += travel.max(0.0);
+                        let t = if length > 0.0 { self.segment_progress / length } else { 1.0 };
+                        return self.anchor + from.offset.lerp(to.offset, t);
+                    }
+
+                    // reached the next waypoint, spend whatever time is left on the following segment
+                    remaining -= (length - self.segment_progress) / from.speed.max(f32::EPSILON);
+                    self.segment = (self.segment + 1) % points.len();
+                    self.segment_progress = 0.0;
+                    self.pause_left = points[self.segment].pause;
+                }
+                self.anchor + points[self.segment].offset
+            }
+        }
+    }
+}
+
+// MOVES EVERY PLATFORM THAT FOLLOWS A PATH
+pub fn move_platforms_along_paths(
+    mut query: Query<(&mut Transform, &mut PathMover)>,
+    freeze_query: Query<(), With<PlatformFreeze>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds() * platform_time_scale(&freeze_query);
+    for (mut transform, mut mover) in query.iter_mut() {
+        let position = mover.advance(dt);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANCHOR: Vec2 = Vec2::new(300.0, 1000.0);
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(actual.distance(expected) < 0.01, "expected {expected}, got {actual}");
+    }
+
+    fn waypoint(x: f32, y: f32, speed: f32, pause: f32) -> Waypoint {
+        Waypoint {
+            offset: Vec2::new(x, y),
+            speed,
+            pause,
+        }
+    }
+
+    #[test]
+    fn vertical_bobs_around_the_anchor() {
+        let mut mover = PathMover::new(PlatformPath::Vertical { amplitude: 60.0, period: 4.0 }, ANCHOR);
+        assert_near(mover.advance(1.0), ANCHOR + Vec2::new(0.0, 60.0));
+        assert_near(mover.advance(1.0), ANCHOR);
+        assert_near(mover.advance(1.0), ANCHOR + Vec2::new(0.0, -60.0));
+    }
+
+    #[test]
+    fn orbit_starts_at_its_phase() {
+        let path = PlatformPath::Orbit {
+            radii: Vec2::new(80.0, 40.0),
+            period: 4.0,
+            phase: 0.25,
+        };
+        let mut mover = PathMover::new(path, ANCHOR);
+        assert_near(mover.advance(0.0), ANCHOR + Vec2::new(0.0, 40.0));
+        assert_near(mover.advance(1.0), ANCHOR + Vec2::new(-80.0, 0.0));
+    }
+
+    #[test]
+    fn waypoints_wait_out_the_first_pause() {
+        let path = PlatformPath::Waypoints(vec![waypoint(0.0, 0.0, 50.0, 1.0), waypoint(100.0, 0.0, 50.0, 0.0)]);
+        let mut mover = PathMover::new(path, ANCHOR);
+        assert_near(mover.advance(0.5), ANCHOR);
+        // half a second more of pause, then a second of travel
+        assert_near(mover.advance(1.5), ANCHOR + Vec2::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn waypoints_carry_leftover_time_into_the_next_segment() {
+        let path = PlatformPath::Waypoints(vec![
+            waypoint(0.0, 0.0, 50.0, 0.0),
+            waypoint(100.0, 0.0, 100.0, 0.5),
+            waypoint(100.0, 100.0, 25.0, 0.0),
+        ]);
+        let mut mover = PathMover::new(path, ANCHOR);
+        // two seconds to the second waypoint, half a second paused there, then half a second at its speed
+        assert_near(mover.advance(3.0), ANCHOR + Vec2::new(100.0, 50.0));
+        // the rest of that segment, then two seconds back towards the first waypoint at the third one's speed
+        let back = Vec2::splat(-50.0 / 2.0_f32.sqrt());
+        assert_near(mover.advance(2.5), ANCHOR + Vec2::new(100.0, 100.0) + back);
+    }
+
+    #[test]
+    fn waypoints_loop_back_to_the_first() {
+        let path = PlatformPath::Waypoints(vec![waypoint(0.0, 0.0, 100.0, 0.0), waypoint(100.0, 0.0, 100.0, 0.0)]);
+        let mut mover = PathMover::new(path, ANCHOR);
+        assert_near(mover.advance(1.5), ANCHOR + Vec2::new(50.0, 0.0));
+        assert_near(mover.advance(0.75), ANCHOR + Vec2::new(25.0, 0.0));
+    }
+
+    #[test]
+    fn degenerate_waypoints_stay_put() {
+        let single = PlatformPath::Waypoints(vec![waypoint(20.0, 10.0, 50.0, 0.0)]);
+        assert_near(PathMover::new(single, ANCHOR).advance(5.0), ANCHOR + Vec2::new(20.0, 10.0));
+        let stacked = PlatformPath::Waypoints(vec![waypoint(20.0, 10.0, 50.0, 0.0), waypoint(20.0, 10.0, 50.0, 0.0)]);
+        assert_near(PathMover::new(stacked, ANCHOR).advance(5.0), ANCHOR + Vec2::new(20.0, 10.0));
+    }
+}
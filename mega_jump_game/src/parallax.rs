@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+const DESERT_MOUNTAINS: &str = "backgrounds/BG_DesertMountains";
+// EVERY BACKGROUND IMAGE IS A SQUARE OF THIS MANY PIXELS, SCALED UP TO THE WIDTH OF THE WINDOW
+const BACKGROUND_SIZE: f32 = 640.0;
+// ONE TILE EITHER SIDE OF THE WINDOW SO A SIDEWAYS SCROLL NEVER SHOWS AN EDGE
+const SIDE_TILES: i32 = 1;
+
+// ONE IMAGE OF A BACKGROUND SET, FARTHEST FIRST, follow IS THE FRACTION OF THE CAMERA'S MOVEMENT IT KEEPS UP WITH
+pub struct LayerDesc {
+    pub image: &'static str,
+    pub follow: Vec2,
+    pub z: f32,
+}
+
+// THE SKY, THE FAR MOUNTAINS AND THE NEAR MOUNTAINS, THE NEARER THE LAYER THE FASTER IT SLIDES PAST
+pub const DESERT_LAYERS: [LayerDesc; 3] = [
+    LayerDesc {
+        image: "background1.png",
+        follow: Vec2::new(0.95, 0.95),
+        z: -10.0,
+    },
+    LayerDesc {
+        image: "background2.png",
+        follow: Vec2::new(0.85, 0.8),
+        z: -9.0,
+    },
+    LayerDesc {
+        image: "background3.png",
+        follow: Vec2::new(0.7, 0.6),
+        z: -8.0,
+    },
+];
+
+// A GRID OF TILES OF ONE BACKGROUND IMAGE THAT SCROLLS BY (1 - follow) OF THE CAMERA'S MOVEMENT AND WRAPS AROUND
+// anchor IS WHERE THE CAMERA SEES THE TILES LINED UP WITH THE WINDOW, index IS THE LAYER'S PLACE IN ITS SET
+#[derive(Component)]
+pub struct ParallaxLayer {
+    pub index: usize,
+    pub follow: Vec2,
+    pub tile_size: Vec2,
+    pub anchor: Vec2,
+}
+
+// ONE COPY OF A LAYER'S IMAGE
+#[derive(Component)]
+pub struct ParallaxTile;
+
+// SPAWNS A LAYER WITH ENOUGH TILES TO COVER THE WINDOW WHEREVER THE WRAP PUTS THEM
+pub fn spawn_parallax_layer(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    window: &Window,
+    folder: &str,
+    index: usize,
+    layer: &LayerDesc,
+) -> Entity {
+    let scale = window.width() / BACKGROUND_SIZE;
+    let tile_size = Vec2::splat(BACKGROUND_SIZE * scale);
+    let rows = (window.height() / tile_size.y).ceil() as i32 + 1;
+    let texture: Handle<Image> = asset_server.load(format!("{}/{}", folder, layer.image));
+    let anchor = Vec2::new(window.width() / 2.0, window.height() / 2.0);
+
+    commands
+        .spawn((
+            ParallaxLayer {
+                index,
+                follow: layer.follow,
+                tile_size,
+                anchor,
+            },
+            SpatialBundle::from_transform(Transform::from_xyz(anchor.x, anchor.y, layer.z)),
+        ))
+        .with_children(|parent| {
+            for column in -SIDE_TILES..=SIDE_TILES {
+                for row in -1..rows {
+                    parent.spawn((
+                        SpriteBundle {
+                            texture: texture.clone(),
+                            sprite: Sprite {
+                                custom_size: Some(tile_size),
+                                ..default()
+                            },
+                            transform: Transform::from_xyz(
+                                column as f32 * tile_size.x,
+                                row as f32 * tile_size.y,
+                                0.0,
+                            ),
+                            ..default()
+                        },
+                        ParallaxTile,
+                    ));
+                }
+            }
+        })
+        .id()
+}
+
+// THE DESERT MOUNTAINS BACKDROP THE CLIMB STARTS IN
+pub fn spawn_parallax_background(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    asset_server: Res<AssetServer>,
+) {
+    let window: &Window = window_query.get_single().unwrap();
+    for (index, layer) in DESERT_LAYERS.iter().enumerate() {
+        spawn_parallax_layer(&mut commands, &asset_server, window, DESERT_MOUNTAINS, index, layer);
+    }
+}
+
+// MOVES EVERY LAYER WITH ITS SHARE OF THE CAMERA AND WRAPS IT BY WHOLE TILES SO IT ALWAYS SURROUNDS THE CAMERA
+pub fn scroll_parallax(
+    camera_query: Query<&Transform, With<Camera2d>>,
+    mut layer_query: Query<(&ParallaxLayer, &mut Transform), Without<Camera2d>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let camera = camera_transform.translation.truncate();
+
+    for (layer, mut transform) in layer_query.iter_mut() {
+        // how far the image has slid across the window since the camera was at the anchor
+        let slide = (camera - layer.anchor) * (Vec2::ONE - layer.follow);
+        let wrapped = Vec2::new(slide.x.rem_euclid(layer.tile_size.x), slide.y.rem_euclid(layer.tile_size.y));
+        let position = camera - wrapped;
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
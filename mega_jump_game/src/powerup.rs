@@ -0,0 +1,329 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::SoundEffect;
+use crate::feel::ScreenFlash;
+use crate::score::{HUD_FONT, HUD_FONT_SIZE};
+use crate::{Jump, Player, JUMP_FORCE};
+
+pub const POWER_UP_RADIUS: f32 = 18.0;
+const JETPACK_TIME: f32 = 3.0;
+const JETPACK_SPEED: f32 = 700.0;
+const SHIELD_TIME: f32 = 15.0;
+const DOUBLE_JUMP_TIME: f32 = 15.0;
+const FREEZE_TIME: f32 = 5.0;
+// WHILE A FREEZE IS ACTIVE MOVING PLATFORMS AND ENEMIES RUN AT THIS FRACTION OF THEIR SPEED
+const FREEZE_SLOWDOWN: f32 = 0.25;
+// HOW STRONGLY A PICKUP FLASHES THE SCREEN IN ITS OWN COLOR
+const PICKUP_FLASH_ALPHA: f32 = 0.3;
+
+// EVERY KIND OF PICKUP, EACH GIVES THE PLAYER ITS OWN TIMED EFFECT
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    // CARRIES THE PLAYER STRAIGHT UP
+    Jetpack,
+    // ABSORBS THE NEXT HIT
+    Shield,
+    // ALLOWS ONE EXTRA JUMP IN MID AIR
+    DoubleJump,
+    // SLOWS EVERY MOVING PLATFORM AND ENEMY RIGHT DOWN
+    Freeze,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Jetpack,
+        PowerUpKind::Shield,
+        PowerUpKind::DoubleJump,
+        PowerUpKind::Freeze,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerUpKind::Jetpack => Jetpack::NAME,
+            PowerUpKind::Shield => Shield::NAME,
+            PowerUpKind::DoubleJump => DoubleJump::NAME,
+            PowerUpKind::Freeze => PlatformFreeze::NAME,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::Jetpack => Color::ORANGE_RED,
+            PowerUpKind::Shield => Color::CYAN,
+            PowerUpKind::DoubleJump => Color::GOLD,
+            PowerUpKind::Freeze => Color::ALICE_BLUE,
+        }
+    }
+}
+
+// A PICKUP WAITING TO BE COLLECTED
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+}
+
+// ONE PICKUP AS WRITTEN IN A LEVEL FILE, OFFSET IS MEASURED FROM THE CENTRE OF THE WINDOW
+#[derive(Deserialize, Clone)]
+pub struct PowerUpDesc {
+    pub offset: Vec2,
+    pub kind: PowerUpKind,
+}
+
+// AN EFFECT ON THE PLAYER THAT WEARS OFF, NAME AND HUD_LINE PLACE IT ON THE EFFECT HUD
+pub trait TimedEffect: Component {
+    const NAME: &'static str;
+    const HUD_LINE: usize;
+    fn remaining(&self) -> f32;
+    fn remaining_mut(&mut self) -> &mut f32;
+}
+
+#[derive(Component)]
+pub struct Jetpack {
+    pub remaining: f32,
+}
+
+#[derive(Component)]
+pub struct Shield {
+    pub remaining: f32,
+}
+
+#[derive(Component)]
+pub struct DoubleJump {
+    pub remaining: f32,
+    pub used: bool, // cleared again on landing
+}
+
+#[derive(Component)]
+pub struct PlatformFreeze {
+    pub remaining: f32,
+}
+
+impl TimedEffect for Jetpack {
+    const NAME: &'static str = "Jetpack";
+    const HUD_LINE: usize = 0;
+    fn remaining(&self) -> f32 {
+        self.remaining
+    }
+    fn remaining_mut(&mut self) -> &mut f32 {
+        &mut self.remaining
+    }
+}
+
+impl TimedEffect for Shield {
+    const NAME: &'static str = "Shield";
+    const HUD_LINE: usize = 1;
+    fn remaining(&self) -> f32 {
+        self.remaining
+    }
+    fn remaining_mut(&mut self) -> &mut f32 {
+        &mut self.remaining
+    }
+}
+
+impl TimedEffect for DoubleJump {
+    const NAME: &'static str = "Double jump";
+    const HUD_LINE: usize = 2;
+    fn remaining(&self) -> f32 {
+        self.remaining
+    }
+    fn remaining_mut(&mut self) -> &mut f32 {
+        &mut self.remaining
+    }
+}
+
+impl TimedEffect for PlatformFreeze {
+    const NAME: &'static str = "Freeze";
+    const HUD_LINE: usize = 3;
+    fn remaining(&self) -> f32 {
+        self.remaining
+    }
+    fn remaining_mut(&mut self) -> &mut f32 {
+        &mut self.remaining
+    }
+}
+
+// TEXT IN THE BOTTOM LEFT CORNER LISTING THE ACTIVE EFFECTS AND THEIR TIME LEFT
+#[derive(Component)]
+pub struct EffectText;
+
+pub fn spawn_power_up(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    origin: Vec2,
+    power_up: &PowerUpDesc,
+) -> Entity {
+    let position = origin + power_up.offset;
+    commands
+        .spawn((
+            PowerUp {
+                kind: power_up.kind,
+            },
+            Collider::ball(POWER_UP_RADIUS),
+            Sensor,
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Mesh::from(shape::Circle::new(POWER_UP_RADIUS))).into(),
+                material: materials.add(ColorMaterial::from(power_up.kind.color())),
+                transform: Transform::from_xyz(position.x, position.y, 0.2),
+                ..default()
+            },
+        ))
+        .id()
+}
+
+// PUTS A POWER UP'S EFFECT ON THE PLAYER, RESTARTING ITS TIMER IF IT IS ALREADY ACTIVE
+pub fn grant_power_up(player: &mut EntityCommands, kind: PowerUpKind) {
+    match kind {
+        PowerUpKind::Jetpack => player.insert(Jetpack {
+            remaining: JETPACK_TIME,
+        }),
+        PowerUpKind::Shield => player.insert(Shield {
+            remaining: SHIELD_TIME,
+        }),
+        PowerUpKind::DoubleJump => player.insert(DoubleJump {
+            remaining: DOUBLE_JUMP_TIME,
+            used: false,
+        }),
+        PowerUpKind::Freeze => player.insert(PlatformFreeze {
+            remaining: FREEZE_TIME,
+        }),
+    };
+}
+
+// GIVES THE PLAYER THE EFFECT OF EVERY PICKUP THEY TOUCH, PICKING UP THE SAME KIND AGAIN RESTARTS ITS TIMER
+pub fn collect_power_ups(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    player_query: Query<Entity, With<Player>>,
+    power_up_query: Query<&PowerUp>,
+    mut sound_events: EventWriter<SoundEffect>,
+    mut flash_events: EventWriter<ScreenFlash>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for (collider1, collider2, intersecting) in rapier_context.intersections_with(player_entity) {
+        if !intersecting {
+            continue;
+        }
+        let other = if collider1 == player_entity { collider2 } else { collider1 };
+        let Ok(power_up) = power_up_query.get(other) else {
+            continue;
+        };
+
+        grant_power_up(&mut commands.entity(player_entity), power_up.kind);
+        commands.entity(other).despawn_recursive();
+        sound_events.send(SoundEffect::PowerUp);
+        flash_events.send(ScreenFlash {
+            color: power_up.kind.color().with_a(PICKUP_FLASH_ALPHA),
+        });
+    }
+}
+
+// COUNTS AN EFFECT DOWN AND TAKES IT OFF THE PLAYER ONCE IT RUNS OUT
+pub fn tick_effect<T: TimedEffect>(
+    mut commands: Commands,
+    mut effect_query: Query<(Entity, &mut T)>,
+    time: Res<Time>,
+) {
+    for (entity, mut effect) in effect_query.iter_mut() {
+        let remaining = effect.remaining_mut();
+        *remaining -= time.delta_seconds();
+        if *remaining <= 0.0 {
+            commands.entity(entity).remove::<T>();
+        }
+    }
+}
+
+// KEEPS THE PLAYER RISING AT A STEADY SPEED WHILE THE JETPACK LASTS
+pub fn fly_jetpack(mut player_query: Query<&mut Velocity, (With<Player>, With<Jetpack>)>) {
+    for mut velocity in player_query.iter_mut() {
+        velocity.linvel.y = JETPACK_SPEED;
+    }
+}
+
+// LETS A FRESH SPACE PRESS IN MID AIR JUMP ONCE MORE, THE JUMP IS GIVEN BACK ON LANDING
+pub fn double_jump(
+    mut commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut player_query: Query<(Entity, &mut Player, &mut Velocity, &mut DoubleJump)>,
+    mut sound_events: EventWriter<SoundEffect>,
+) {
+    let Ok((entity, mut player, mut velocity, mut double_jump)) = player_query.get_single_mut() else {
+        return;
+    };
+    if player.grounded_on.is_some() {
+        double_jump.used = false;
+        return;
+    }
+    // move_player clears the buffer when it used this press for an ordinary jump
+    if double_jump.used || !keyboard.just_pressed(KeyCode::Space) || player.jump_buffer <= 0.0 {
+        return;
+    }
+
+    double_jump.used = true;
+    player.jump_buffer = 0.0;
+    velocity.linvel.y = JUMP_FORCE;
+    commands.entity(entity).insert(Jump {
+        held: true,
+        launched: false,
+    });
+    sound_events.send(SoundEffect::Jump);
+}
+
+// HOW FAST MOVING PLATFORMS AND ENEMIES RUN RIGHT NOW, 1.0 UNLESS A FREEZE IS SLOWING THEM DOWN
+pub fn platform_time_scale(freeze_query: &Query<(), With<PlatformFreeze>>) -> f32 {
+    if freeze_query.is_empty() {
+        1.0
+    } else {
+        FREEZE_SLOWDOWN
+    }
+}
+
+pub fn spawn_effect_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextStyle {
+        font: asset_server.load(HUD_FONT),
+        font_size: HUD_FONT_SIZE,
+        color: Color::WHITE,
+    };
+
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::from_style(style.clone()),
+            TextSection::from_style(style.clone()),
+            TextSection::from_style(style.clone()),
+            TextSection::from_style(style),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        EffectText,
+    ));
+}
+
+// WRITES ONE EFFECT'S TIME LEFT ON ITS OWN LINE OF THE EFFECT HUD, OR CLEARS THE LINE WHEN IT IS NOT ACTIVE
+pub fn update_effect_hud<T: TimedEffect>(
+    effect_query: Query<&T>,
+    mut text_query: Query<&mut Text, With<EffectText>>,
+) {
+    let line = match effect_query.get_single() {
+        Ok(effect) => format!("{} {:.1}s\n", T::NAME, effect.remaining().max(0.0)),
+        Err(_) => String::new(),
+    };
+    for mut text in text_query.iter_mut() {
+        if text.sections[T::HUD_LINE].value != line {
+            text.sections[T::HUD_LINE].value = line.clone();
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::Player;
+
+pub const HUD_FONT: &str = "fonts/pricedown bl.otf";
+pub const HUD_FONT_SIZE: f32 = 36.0;
+// HOW MANY PIXELS OF CLIMB COUNT AS ONE METRE ON THE HUD
+pub const PIXELS_PER_METRE: f32 = 100.0;
+// DISTANCE FROM THE CENTRE OF THE STARTING PLATFORM TO THE PLAYER'S CENTRE WHEN STANDING ON IT
+const STANDING_OFFSET: f32 = 15.0 + 49.0;
+
+// CLIMB PROGRESS OF THE CURRENT RUN, HEIGHTS ARE IN PIXELS ABOVE THE STARTING PLATFORM
+#[derive(Resource, Default)]
+pub struct Score {
+    pub current: f32,
+    pub best: f32,
+    pub elapsed: f32,
+    pub coins: u32,
+}
+
+#[derive(Component)]
+pub struct HudText;
+
+// TEXT IN THE TOP LEFT CORNER SHOWING HEIGHT, BEST HEIGHT, TIME AND COINS
+pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextStyle {
+        font: asset_server.load(HUD_FONT),
+        font_size: HUD_FONT_SIZE,
+        color: Color::WHITE,
+    };
+
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new("Height: ", style.clone()),
+            TextSection::from_style(style.clone()),
+            TextSection::new("\nBest: ", style.clone()),
+            TextSection::from_style(style.clone()),
+            TextSection::new("\nTime: ", style.clone()),
+            TextSection::from_style(style.clone()),
+            TextSection::new("\nCoins: ", style.clone()),
+            TextSection::from_style(style),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        HudText,
+    ));
+}
+
+// MEASURES HOW HIGH THE PLAYER IS ABOVE THE STARTING PLATFORM AND KEEPS THE BEST HEIGHT
+pub fn update_score(
+    mut score: ResMut<Score>,
+    player_query: Query<&Transform, With<Player>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    let window: &Window = window_query.get_single().unwrap();
+    let start_y = window.height() / 2.0 + STANDING_OFFSET;
+
+    score.elapsed += time.delta_seconds();
+    if let Ok(player_transform) = player_query.get_single() {
+        score.current = (player_transform.translation.y - start_y).max(0.0);
+        score.best = score.best.max(score.current);
+    }
+}
+
+pub fn update_hud(score: Res<Score>, mut hud_query: Query<&mut Text, With<HudText>>) {
+    if !score.is_changed() {
+        return;
+    }
+    for mut text in hud_query.iter_mut() {
+        text.sections[1].value = format!("{:.0} m", score.current / PIXELS_PER_METRE);
+        text.sections[3].value = format!("{:.0} m", score.best / PIXELS_PER_METRE);
+        text.sections[5].value = format!(
+            "{}:{:02}",
+            score.elapsed as u32 / 60,
+            score.elapsed as u32 % 60
+        );
+        text.sections[7].value = score.coins.to_string();
+    }
+}
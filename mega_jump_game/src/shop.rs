@@ -0,0 +1,297 @@
+use std::fs;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::powerup::{grant_power_up, PowerUpKind};
+use crate::score::HUD_FONT;
+use crate::state::GameState;
+use crate::Player;
+
+// WHERE THE WALLET IS KEPT BETWEEN SESSIONS, RELATIVE TO THE WORKING DIRECTORY
+pub const WALLET_PATH: &str = "wallet.ron";
+// A WALLET THAT CAN'T BE READ IS MOVED HERE BEFORE A FRESH ONE IS SAVED OVER IT, OR TO .bak.1, .bak.2... IF TAKEN
+const WALLET_BACKUP_PATH: &str = "wallet.ron.bak";
+const SHOP_FONT_SIZE: f32 = 36.0;
+const SHOP_TITLE_FONT_SIZE: f32 = 96.0;
+const SHOP_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.8);
+
+// TINTS THE PLAYER SPRITE CAN BE BOUGHT IN
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Cosmetic {
+    Crimson,
+    Ocean,
+    Golden,
+}
+
+impl Cosmetic {
+    pub fn color(&self) -> Color {
+        match self {
+            Cosmetic::Crimson => Color::rgb(1.0, 0.55, 0.55),
+            Cosmetic::Ocean => Color::rgb(0.55, 0.75, 1.0),
+            Cosmetic::Golden => Color::rgb(1.0, 0.85, 0.3),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Cosmetic::Crimson => "Crimson tint",
+            Cosmetic::Ocean => "Ocean tint",
+            Cosmetic::Golden => "Golden tint",
+        }
+    }
+}
+
+// COINS AND PURCHASES THAT CARRY OVER FROM RUN TO RUN, SAVED TO WALLET_PATH
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct Wallet {
+    pub coins: u32,
+    #[serde(default)]
+    pub owned: Vec<Cosmetic>,
+    #[serde(default)]
+    pub equipped: Option<Cosmetic>,
+    // POWER UPS BOUGHT FOR THE NEXT RUN, USED UP WHEN IT STARTS
+    #[serde(default)]
+    pub starting_power_ups: Vec<PowerUpKind>,
+    // SET WHEN AN UNREADABLE WALLET COULD NOT BE MOVED ASIDE, SO IT IS NEVER SAVED OVER
+    #[serde(skip)]
+    read_only: bool,
+}
+
+impl Wallet {
+    // A MISSING FILE STARTS AN EMPTY WALLET, AN UNREADABLE ONE IS MOVED TO A FREE BACKUP PATH FIRST
+    pub fn load() -> Self {
+        let contents = match fs::read_to_string(WALLET_PATH) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(error) => {
+                warn!("Could not read the wallet from {}: {}", WALLET_PATH, error);
+                return Self {
+                    read_only: true,
+                    ..default()
+                };
+            }
+        };
+        match ron::from_str(&contents) {
+            Ok(wallet) => wallet,
+            Err(error) => {
+                warn!("Could not parse the wallet in {}: {}", WALLET_PATH, error);
+                // keep the old coins around to fix by hand instead of losing them on the next save
+                let backup_path = free_backup_path();
+                let read_only = match fs::rename(WALLET_PATH, &backup_path) {
+                    Ok(()) => {
+                        warn!("Moved the unreadable wallet to {}", backup_path);
+                        false
+                    }
+                    Err(error) => {
+                        warn!("Could not move the wallet to {}, it won't be saved: {}", backup_path, error);
+                        true
+                    }
+                };
+                Self {
+                    read_only,
+                    ..default()
+                }
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        if self.read_only {
+            return;
+        }
+        match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => {
+                if let Err(error) = fs::write(WALLET_PATH, contents) {
+                    warn!("Could not save the wallet to {}: {}", WALLET_PATH, error);
+                }
+            }
+            Err(error) => warn!("Could not serialize the wallet: {}", error),
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        self.equipped.map_or(Color::WHITE, |cosmetic| cosmetic.color())
+    }
+}
+
+// SOMETHING ON SALE IN THE SHOP
+#[derive(Clone, Copy)]
+enum ShopItem {
+    Cosmetic(Cosmetic),
+    PowerUp(PowerUpKind),
+}
+
+// EVERYTHING ON SALE WITH ITS PRICE, BOUGHT WITH THE NUMBER KEY OF ITS PLACE IN THE LIST
+const SHOP_ITEMS: [(ShopItem, u32); 6] = [
+    (ShopItem::Cosmetic(Cosmetic::Crimson), 20),
+    (ShopItem::Cosmetic(Cosmetic::Ocean), 20),
+    (ShopItem::Cosmetic(Cosmetic::Golden), 60),
+    (ShopItem::PowerUp(PowerUpKind::Shield), 10),
+    (ShopItem::PowerUp(PowerUpKind::DoubleJump), 15),
+    (ShopItem::PowerUp(PowerUpKind::Jetpack), 25),
+];
+
+const ITEM_KEYS: [KeyCode; 6] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+];
+
+#[derive(Component)]
+pub struct ShopScreen;
+
+// THE FIRST BACKUP PATH NOT ALREADY HOLDING AN EARLIER UNREADABLE WALLET
+fn free_backup_path() -> String {
+    std::iter::once(WALLET_BACKUP_PATH.to_string())
+        .chain((1..).map(|n| format!("{WALLET_BACKUP_PATH}.{n}")))
+        .find(|path| !std::path::Path::new(path).exists())
+        .unwrap()
+}
+
+// THE PRICE LIST, REWRITTEN WHENEVER THE WALLET CHANGES
+#[derive(Component)]
+pub struct ShopText;
+
+pub fn save_wallet(wallet: Res<Wallet>) {
+    wallet.save();
+}
+
+// SAVES THE WALLET WHEN THE GAME IS CLOSED, SO COINS PICKED UP IN THE RUN BEING PLAYED AREN'T LOST
+// runs in CoreSet::Last, the app stops right after the frame that sent AppExit
+pub fn save_wallet_on_exit(mut exit_events: EventReader<AppExit>, wallet: Res<Wallet>) {
+    if exit_events.iter().count() > 0 {
+        wallet.save();
+    }
+}
+
+// TINTS A NEWLY SPAWNED PLAYER AND GIVES THEM THE POWER UPS BOUGHT FOR THIS RUN
+pub fn equip_new_player(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut TextureAtlasSprite), Added<Player>>,
+    mut wallet: ResMut<Wallet>,
+) {
+    let Ok((entity, mut sprite)) = player_query.get_single_mut() else {
+        return;
+    };
+    sprite.color = wallet.tint();
+
+    let mut player = commands.entity(entity);
+    for kind in wallet.starting_power_ups.drain(..) {
+        grant_power_up(&mut player, kind);
+    }
+}
+
+fn item_label(index: usize, wallet: &Wallet) -> String {
+    let (item, price) = SHOP_ITEMS[index];
+    let (name, status) = match item {
+        ShopItem::Cosmetic(cosmetic) if wallet.equipped == Some(cosmetic) => (cosmetic.name(), "equipped"),
+        ShopItem::Cosmetic(cosmetic) if wallet.owned.contains(&cosmetic) => (cosmetic.name(), "owned"),
+        ShopItem::Cosmetic(cosmetic) => (cosmetic.name(), ""),
+        ShopItem::PowerUp(kind) if wallet.starting_power_ups.contains(&kind) => (kind.name(), "ready"),
+        ShopItem::PowerUp(kind) => (kind.name(), ""),
+    };
+    if status.is_empty() {
+        format!("{}  {}  {} coins\n", index + 1, name, price)
+    } else {
+        format!("{}  {}  {}\n", index + 1, name, status)
+    }
+}
+
+fn shop_label(wallet: &Wallet) -> String {
+    let mut label = format!("Coins: {}\n\n", wallet.coins);
+    for index in 0..SHOP_ITEMS.len() {
+        label += &item_label(index, wallet);
+    }
+    label += "\nNumber keys buy or equip, Escape for the menu";
+    label
+}
+
+pub fn spawn_shop_screen(mut commands: Commands, asset_server: Res<AssetServer>, wallet: Res<Wallet>) {
+    let font = asset_server.load(HUD_FONT);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: SHOP_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            ShopScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Shop",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: SHOP_TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    shop_label(&wallet),
+                    TextStyle {
+                        font,
+                        font_size: SHOP_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                ),
+                ShopText,
+            ));
+        });
+}
+
+pub fn update_shop_text(wallet: Res<Wallet>, mut text_query: Query<&mut Text, With<ShopText>>) {
+    if !wallet.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = shop_label(&wallet);
+    }
+}
+
+// BUYS, EQUIPS OR UNEQUIPS THE ITEM WHOSE NUMBER WAS PRESSED, SAVING THE WALLET AFTER EVERY CHANGE
+pub fn shop_input(
+    keyboard: Res<Input<KeyCode>>,
+    mut wallet: ResMut<Wallet>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+        return;
+    }
+    let Some(index) = ITEM_KEYS.iter().position(|key| keyboard.just_pressed(*key)) else {
+        return;
+    };
+
+    let (item, price) = SHOP_ITEMS[index];
+    match item {
+        ShopItem::Cosmetic(cosmetic) if wallet.owned.contains(&cosmetic) => {
+            wallet.equipped = if wallet.equipped == Some(cosmetic) { None } else { Some(cosmetic) };
+        }
+        ShopItem::PowerUp(kind) if wallet.starting_power_ups.contains(&kind) => return,
+        _ if wallet.coins < price => return,
+        ShopItem::Cosmetic(cosmetic) => {
+            wallet.coins -= price;
+            wallet.owned.push(cosmetic);
+            wallet.equipped = Some(cosmetic);
+        }
+        ShopItem::PowerUp(kind) => {
+            wallet.coins -= price;
+            wallet.starting_power_ups.push(kind);
+        }
+    }
+    wallet.save();
+}
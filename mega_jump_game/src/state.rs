@@ -0,0 +1,297 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::classic::GameMode;
+use crate::coin::{Coin, CoinPickup};
+use crate::hazard::Hazard;
+use crate::map::{CurrentLevel, Platform};
+use crate::powerup::PowerUp;
+use crate::score::{HUD_FONT, PIXELS_PER_METRE, Score};
+use crate::{Player, PlayerState};
+
+// WHAT THE GAME IS CURRENTLY DOING, GAMEPLAY SYSTEMS ONLY RUN WHILE Playing
+#[derive(States, Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+    Victory,
+    Shop,
+}
+
+// EVERYTHING THAT BELONGS TO A SINGLE RUN AND IS THROWN AWAY ON RESTART, CHILDREN GO WITH THEIR PARENT
+pub type RunEntity = (
+    Or<(With<Player>, With<Platform>, With<Hazard>, With<PowerUp>, With<Coin>, With<CoinPickup>)>,
+    Without<Parent>,
+);
+
+const TITLE_FONT_SIZE: f32 = 96.0;
+const SUBTITLE_FONT_SIZE: f32 = 40.0;
+const OVERLAY_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+
+#[derive(Component)]
+pub struct MainMenuScreen;
+
+// LINE ON THE MAIN MENU SHOWING THE SELECTED GameMode
+#[derive(Component)]
+pub struct ModeText;
+
+#[derive(Component)]
+pub struct PauseScreen;
+
+#[derive(Component)]
+pub struct GameOverScreen;
+
+#[derive(Component)]
+pub struct VictoryScreen;
+
+// SPAWNS A FULL SCREEN OVERLAY WITH A TITLE AND A LINE OF INSTRUCTIONS UNDER IT
+fn spawn_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    marker: impl Component,
+    title: &str,
+    subtitle: &str,
+) -> Entity {
+    let font = asset_server.load(HUD_FONT);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: OVERLAY_COLOR.into(),
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                title,
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    subtitle,
+                    TextStyle {
+                        font,
+                        font_size: SUBTITLE_FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center),
+            );
+        })
+        .id()
+}
+
+// DESPAWNS EVERY ENTITY WITH THE GIVEN MARKER, USED TO TEAR DOWN SCREENS ON STATE EXIT
+pub fn despawn_with<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// REMOVES THE PLAYER AND ALL PLATFORMS SO THE NEXT RUN STARTS FROM A CLEAN WORLD
+pub fn despawn_run(
+    mut commands: Commands,
+    query: Query<Entity, RunEntity>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// A NEW RUN STARTS WHENEVER Playing IS ENTERED WITHOUT A PLAYER IN THE WORLD
+pub fn no_run_in_progress(player_query: Query<(), With<Player>>) -> bool {
+    player_query.is_empty()
+}
+
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+) {
+    let screen = spawn_screen(
+        &mut commands,
+        &asset_server,
+        MainMenuScreen,
+        "Mega Jump",
+        "Press Enter to start\nS for the shop",
+    );
+    let mode_text = commands
+        .spawn((
+            TextBundle::from_section(
+                mode_label(*game_mode),
+                TextStyle {
+                    font: asset_server.load(HUD_FONT),
+                    font_size: SUBTITLE_FONT_SIZE,
+                    color: Color::WHITE,
+                },
+            ),
+            ModeText,
+        ))
+        .id();
+    commands.entity(screen).add_child(mode_text);
+}
+
+fn mode_label(game_mode: GameMode) -> String {
+    format!("C to change mode: {}", game_mode.name())
+}
+
+pub fn update_mode_text(game_mode: Res<GameMode>, mut text_query: Query<&mut Text, With<ModeText>>) {
+    if !game_mode.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = mode_label(*game_mode);
+    }
+}
+
+pub fn spawn_pause_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(
+        &mut commands,
+        &asset_server,
+        PauseScreen,
+        "Paused",
+        "Escape to resume\nR to restart\nQ to quit to the menu",
+    );
+}
+
+pub fn spawn_game_over_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+) {
+    spawn_screen(
+        &mut commands,
+        &asset_server,
+        GameOverScreen,
+        "Game Over",
+        &format!(
+            "Height: {:.0} m   Best: {:.0} m\nEnter to restart\nEscape for the menu",
+            score.current / PIXELS_PER_METRE,
+            score.best / PIXELS_PER_METRE
+        ),
+    );
+}
+
+pub fn spawn_victory_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(
+        &mut commands,
+        &asset_server,
+        VictoryScreen,
+        "You made it!",
+        "Enter to keep climbing\nR to restart\nEscape for the menu",
+    );
+}
+
+pub fn main_menu_input(
+    keyboard: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    if keyboard.any_just_pressed([KeyCode::Return, KeyCode::Space]) {
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::C) {
+        *game_mode = game_mode.toggled();
+    } else if keyboard.just_pressed(KeyCode::S) {
+        next_state.set(GameState::Shop);
+    }
+}
+
+pub fn playing_input(keyboard: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Paused);
+    }
+}
+
+pub fn paused_input(
+    commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    run_query: Query<Entity, RunEntity>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::R) {
+        despawn_run(commands, run_query);
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::Q) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+pub fn game_over_input(
+    commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    run_query: Query<Entity, RunEntity>,
+) {
+    if keyboard.any_just_pressed([KeyCode::Return, KeyCode::R]) {
+        despawn_run(commands, run_query);
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+pub fn victory_input(
+    commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    run_query: Query<Entity, RunEntity>,
+) {
+    if keyboard.just_pressed(KeyCode::Return) {
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::R) {
+        despawn_run(commands, run_query);
+        next_state.set(GameState::Playing);
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+// SHOWS THE VICTORY SCREEN THE FIRST TIME THE PLAYER CLIMBS PAST THE TOP OF THE LEVEL FILE
+pub fn check_victory(
+    mut player_query: Query<(&mut Player, &Transform)>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !current_level.spawned || current_level.completed {
+        return;
+    }
+    if let Ok((mut player, player_transform)) = player_query.get_single_mut() {
+        if player_transform.translation.y > current_level.top {
+            current_level.completed = true;
+            player.state = PlayerState::Cheer;
+            next_state.set(GameState::Victory);
+        }
+    }
+}
+
+pub fn reset_score(mut score: ResMut<Score>) {
+    score.current = 0.0;
+    score.elapsed = 0.0;
+    score.coins = 0;
+}
+
+pub fn resume_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = true;
+}
+
+pub fn pause_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = false;
+}